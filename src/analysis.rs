@@ -0,0 +1,189 @@
+use std::{fmt::Debug, hash::Hash};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{determine::epsilon_closure, *};
+
+impl<T> Dfa<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Check if no word is accepted
+    pub fn is_empty(&self) -> bool {
+        !self
+            .reachable_states()
+            .iter()
+            .any(|state| self.accept_states.contains(state))
+    }
+
+    /// Check if finitely many words are accepted
+    pub fn is_finite(&self) -> bool {
+        !self.has_useful_cycle(|_| true)
+    }
+
+    /// Check if every word over `alphabet` is accepted
+    ///
+    /// Only the states reachable through `alphabet` symbols have to accept.
+    pub fn is_universal(&self, alphabet: &[T]) -> bool {
+        let mut to_explore = vec![self.initial_state()];
+        let mut explored = FxHashSet::default();
+
+        while let Some(state) = to_explore.pop() {
+            if !explored.insert(state) {
+                continue;
+            }
+            if !self.accept_states.contains(&state) {
+                return false;
+            }
+            let targets: FxHashMap<&T, State> = self
+                .links_from(state)
+                .map(|link| (&link.symbol, link.to))
+                .collect();
+            for symbol in alphabet {
+                match targets.get(symbol) {
+                    Some(to) => to_explore.push(*to),
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn accepts_empty_string(&self) -> bool {
+        self.accept_states.contains(&self.initial_state())
+    }
+}
+
+impl<T> Nfa<T> {
+    /// Check if no word is accepted
+    pub fn is_empty_nfa(&self) -> bool {
+        !self
+            .reachable_states()
+            .iter()
+            .any(|state| self.accept_states.contains(state))
+    }
+
+    /// Check if finitely many words are accepted
+    ///
+    /// Cycles made of epsilon links only are not taken into account.
+    pub fn is_finite_nfa(&self) -> bool {
+        !self.has_useful_cycle(|symbol| matches!(symbol, MaybeSymbol::Symbol(_)))
+    }
+
+    pub fn accepts_empty_string_nfa(&self) -> bool {
+        epsilon_closure(self, self.initial_states.iter().copied())
+            .iter()
+            .any(|state| self.accept_states.contains(state))
+    }
+}
+
+impl<T> Nfa<T>
+where
+    T: Eq + Hash + Clone + Debug + 'static,
+{
+    /// Check if every word over `alphabet` is accepted
+    ///
+    /// Unlike the other queries, this is not linear: the NFA has to be
+    /// determinized, see [is_subset].
+    pub fn is_universal_nfa(&self, alphabet: &[T]) -> bool {
+        let mut universal = Nfa::new();
+        universal.initial_states.insert(0);
+        universal.accept_states.insert(0);
+        for symbol in alphabet {
+            universal.link(0, 0, MaybeSymbol::Symbol(symbol.clone()));
+        }
+
+        is_subset(&universal, self).is_ok()
+    }
+}
+
+impl<T> Automata<T> {
+    /// Check for a cycle through a link whose symbol `counts`, among the states
+    /// both reachable and coreachable
+    fn has_useful_cycle(&self, counts: impl Fn(&T) -> bool) -> bool {
+        let useful = self.useful_states();
+
+        // A link is part of a cycle if both its ends are in a same component
+        let components = self.components(&useful);
+        self.links().any(|link| {
+            useful.contains(&link.from)
+                && useful.contains(&link.to)
+                && components[&link.from] == components[&link.to]
+                && counts(&link.symbol)
+        })
+    }
+
+    /// Strongly connected component of each state of `states`, considering the
+    /// links between them only (Tarjan's algorithm)
+    fn components(&self, states: &FxHashSet<State>) -> FxHashMap<State, usize> {
+        let successors = |state: State| -> Vec<State> {
+            self.links_from(state)
+                .map(|link| link.to)
+                .filter(|to| states.contains(to))
+                .collect()
+        };
+
+        let mut indices: FxHashMap<State, usize> = Default::default();
+        let mut lowlinks: FxHashMap<State, usize> = Default::default();
+        let mut stack = Vec::new();
+        let mut on_stack = FxHashSet::default();
+        let mut components: FxHashMap<State, usize> = Default::default();
+        let mut component_count = 0;
+
+        for root in states {
+            if indices.contains_key(root) {
+                continue;
+            }
+
+            // Depth-first search without recursion: state, successors, next one
+            let mut work: Vec<(State, Vec<State>, usize)> = Vec::new();
+            let mut to_visit = Some(*root);
+
+            loop {
+                if let Some(state) = to_visit.take() {
+                    indices.insert(state, indices.len());
+                    lowlinks.insert(state, indices[&state]);
+                    stack.push(state);
+                    on_stack.insert(state);
+                    work.push((state, successors(state), 0));
+                }
+
+                let Some((state, targets, next)) = work.last_mut() else {
+                    break;
+                };
+                let state = *state;
+
+                if let Some(to) = targets.get(*next).copied() {
+                    *next += 1;
+                    if !indices.contains_key(&to) {
+                        to_visit = Some(to);
+                    } else if on_stack.contains(&to) {
+                        let lowlink = lowlinks[&state].min(indices[&to]);
+                        lowlinks.insert(state, lowlink);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some((parent, ..)) = work.last() {
+                    let lowlink = lowlinks[parent].min(lowlinks[&state]);
+                    lowlinks.insert(*parent, lowlink);
+                }
+
+                if lowlinks[&state] == indices[&state] {
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        components.insert(member, component_count);
+                        if member == state {
+                            break;
+                        }
+                    }
+                    component_count += 1;
+                }
+            }
+        }
+
+        components
+    }
+}
@@ -1,17 +1,76 @@
-use std::fmt::{Display, Write};
+use std::fmt::{self, Debug, Display, Write};
 
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::Counter;
 
 pub type State = u32;
 
 /// Generic structure behind [Nfa] & [Dfa]
-#[derive(Debug, Clone)]
+///
+/// Links are only edited through methods such as [Automata::link] and
+/// [Automata::remove_links], which keep the adjacency index in sync. They are
+/// read with [Automata::links], automata are built from existing links with
+/// [Automata::from_parts].
+#[derive(Clone)]
 pub struct Automata<T> {
     pub initial_states: FxHashSet<State>,
     pub accept_states: FxHashSet<State>,
-    pub links: Vec<Link<T>>,
+    /// Links in insertion order, `None` for removed ones until the next
+    /// compaction
+    links: Vec<Option<Link<T>>>,
+    index: Index,
+}
+
+/// Positions in `links` of the links leaving and reaching each state
+#[derive(Debug, Clone, Default)]
+struct Index {
+    outgoing: FxHashMap<State, Vec<usize>>,
+    incoming: FxHashMap<State, Vec<usize>>,
+    /// Number of removed links still taking a position
+    removed: usize,
+}
+
+impl Index {
+    fn new<T>(links: &[Option<Link<T>>]) -> Self {
+        let mut index = Index::default();
+        for (position, link) in links.iter().enumerate() {
+            if let Some(link) = link {
+                index.push(position, link);
+            }
+        }
+        index
+    }
+
+    fn push<T>(&mut self, position: usize, link: &Link<T>) {
+        self.outgoing.entry(link.from).or_default().push(position);
+        self.incoming.entry(link.to).or_default().push(position);
+    }
+
+    fn remove<T>(&mut self, position: usize, link: &Link<T>) {
+        for (map, state) in [
+            (&mut self.outgoing, link.from),
+            (&mut self.incoming, link.to),
+        ] {
+            if let Some(positions) = map.get_mut(&state) {
+                positions.retain(|other| *other != position);
+                if positions.is_empty() {
+                    map.remove(&state);
+                }
+            }
+        }
+        self.removed += 1;
+    }
+}
+
+impl<T: Debug> Debug for Automata<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Automata")
+            .field("initial_states", &self.initial_states)
+            .field("accept_states", &self.accept_states)
+            .field("links", &self.links().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,12 +90,20 @@ impl<T> Link<T> {
     }
 }
 
+/// NFA, links may be epsilon links
+///
+/// `Nfa<T>` is `Dfa<MaybeSymbol<T>>`, so a method can't have the same name on
+/// both: a query or operation offered for both automata keeps its plain name
+/// on [Dfa] and takes an `_nfa` suffix on [Nfa], as [Dfa::union] and
+/// [Nfa::union_nfa] or [Dfa::is_empty] and [Nfa::is_empty_nfa]. Methods only
+/// offered for NFAs, such as [Nfa::concat], keep their plain name.
 pub type Nfa<T> = Automata<MaybeSymbol<T>>;
 pub type Dfa<T> = Automata<T>;
 
 pub type NfaLink<T> = Link<MaybeSymbol<T>>;
 pub type DfaLink<T> = Link<T>;
 
+#[derive(Debug, Clone)]
 pub enum MaybeSymbol<T> {
     Symbol(T),
     Epsilon,
@@ -64,11 +131,15 @@ impl<T> Dfa<T> {
 
     /// Cheaply convert DFA as NFA
     pub fn into_nfa(self) -> Nfa<T> {
-        let links = self.links.into_iter().map(Link::into_nfa).collect();
         Nfa {
             initial_states: self.initial_states,
             accept_states: self.accept_states,
-            links,
+            links: self
+                .links
+                .into_iter()
+                .map(|link| link.map(Link::into_nfa))
+                .collect(),
+            index: self.index,
         }
     }
 }
@@ -79,6 +150,7 @@ impl<T> Default for Automata<T> {
             initial_states: Default::default(),
             accept_states: Default::default(),
             links: Default::default(),
+            index: Default::default(),
         }
     }
 }
@@ -88,35 +160,123 @@ impl<T> Automata<T> {
         Self::default()
     }
 
+    /// Create automata from its states and links
+    pub fn from_parts(
+        initial_states: FxHashSet<State>,
+        accept_states: FxHashSet<State>,
+        links: Vec<Link<T>>,
+    ) -> Self {
+        let links: Vec<_> = links.into_iter().map(Some).collect();
+        Self {
+            initial_states,
+            accept_states,
+            index: Index::new(&links),
+            links,
+        }
+    }
+
     pub fn invert(&mut self) {
         std::mem::swap(&mut self.accept_states, &mut self.initial_states);
-        for link in self.links.iter_mut() {
+        for link in self.links.iter_mut().flatten() {
             std::mem::swap(&mut link.from, &mut link.to);
         }
+        let index = &mut self.index;
+        std::mem::swap(&mut index.outgoing, &mut index.incoming);
+    }
+
+    /// Links in insertion order
+    pub fn links(&self) -> impl Iterator<Item = &Link<T>> {
+        self.links.iter().flatten()
+    }
+
+    pub fn link_count(&self) -> usize {
+        self.links.len() - self.index.removed
+    }
+
+    /// Links indexed under `state` in `map`
+    fn indexed<'a>(
+        &'a self,
+        map: &'a FxHashMap<State, Vec<usize>>,
+        state: State,
+    ) -> impl Iterator<Item = &'a Link<T>> {
+        let positions = map.get(&state).map(Vec::as_slice).unwrap_or_default();
+        positions
+            .iter()
+            .map(|position| self.links[*position].as_ref().expect("Indexed link"))
     }
 
     pub fn links_from(&self, from: State) -> impl Iterator<Item = &Link<T>> {
-        self.links.iter().filter(move |link| link.from == from)
+        self.indexed(&self.index.outgoing, from)
     }
 
     pub fn links_to(&self, to: State) -> impl Iterator<Item = &Link<T>> {
-        self.links.iter().filter(move |link| link.to == to)
+        self.indexed(&self.index.incoming, to)
     }
 
     pub fn links_from_to(&self, from: State, to: State) -> impl Iterator<Item = &Link<T>> {
-        self.links
-            .iter()
-            .filter(move |link| link.from == from && link.to == to)
+        self.links_from(from).filter(move |link| link.to == to)
     }
 
     pub fn remove_links(&mut self, from: State, to: State) {
-        self.links
-            .retain(|link| !(link.from == from && link.to == to))
+        let positions: Vec<usize> = self
+            .index
+            .outgoing
+            .get(&from)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|position| self.links[*position].as_ref().is_some_and(|l| l.to == to))
+            .collect();
+        self.remove_positions(positions);
     }
 
     pub fn remove_links_any(&mut self, from_or_to: State) {
+        let mut positions: Vec<usize> = [&self.index.outgoing, &self.index.incoming]
+            .into_iter()
+            .filter_map(|map| map.get(&from_or_to))
+            .flatten()
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        self.remove_positions(positions);
+    }
+
+    /// Remove the links at `positions`, in time proportional to the number of
+    /// links of their states
+    fn remove_positions(&mut self, positions: Vec<usize>) {
+        for position in positions {
+            if let Some(link) = self.links[position].take() {
+                self.index.remove(position, &link);
+            }
+        }
+
+        // Compact once removed links take most of the positions
+        if self.index.removed > self.link_count() {
+            self.links.retain(Option::is_some);
+            self.index = Index::new(&self.links);
+        }
+    }
+
+    /// Keep only the links for which `keep` returns `true`, preserving their order
+    pub fn retain_links(&mut self, mut keep: impl FnMut(&Link<T>) -> bool) {
         self.links
-            .retain(|link| !(link.from == from_or_to || link.to == from_or_to))
+            .retain(|link| link.as_ref().is_some_and(&mut keep));
+        self.index = Index::new(&self.links);
+    }
+
+    /// Remove and return all links
+    pub fn take_links(&mut self) -> Vec<Link<T>> {
+        self.index = Default::default();
+        std::mem::take(&mut self.links)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Consume the automaton into its links
+    pub fn into_links(self) -> Vec<Link<T>> {
+        self.links.into_iter().flatten().collect()
     }
 
     pub fn max_state(&self) -> Option<State> {
@@ -137,42 +297,41 @@ impl<T> Automata<T> {
             .iter()
             .copied()
             .chain(self.accept_states.iter().copied())
-            .chain(self.links.iter().flat_map(|l| [l.from, l.to]))
+            .chain(self.links().flat_map(|l| [l.from, l.to]))
     }
 
     pub fn non_special_states(&self) -> impl Iterator<Item = State> + '_ {
-        self.links
-            .iter()
+        self.links()
             .flat_map(|l| [l.from, l.to])
             .filter(|s| !self.initial_states.contains(s) && !self.accept_states.contains(s))
     }
 
     pub fn link(&mut self, from: State, to: State, symbol: T) {
-        self.links.push(Link { from, to, symbol })
+        let link = Link { from, to, symbol };
+        self.index.push(self.links.len(), &link);
+        self.links.push(Some(link));
     }
 
     pub fn map<F>(&self, mut mapper: impl FnMut(&T) -> F) -> Automata<F> {
-        Automata {
-            initial_states: self.initial_states.clone(),
-            accept_states: self.accept_states.clone(),
-            links: self
-                .links
-                .iter()
+        Automata::from_parts(
+            self.initial_states.clone(),
+            self.accept_states.clone(),
+            self.links()
                 .map(|link| Link {
                     from: link.from,
                     to: link.to,
                     symbol: mapper(&link.symbol),
                 })
                 .collect(),
-        }
+        )
     }
 
-    pub fn into_map<F>(self, mut mapper: impl FnMut(T) -> F) -> Automata<F> {
-        Automata {
-            initial_states: self.initial_states,
-            accept_states: self.accept_states,
-            links: self
-                .links
+    pub fn into_map<F>(mut self, mut mapper: impl FnMut(T) -> F) -> Automata<F> {
+        let links = self.take_links();
+        Automata::from_parts(
+            self.initial_states,
+            self.accept_states,
+            links
                 .into_iter()
                 .map(|link| Link {
                     from: link.from,
@@ -180,6 +339,6 @@ impl<T> Automata<T> {
                     symbol: mapper(link.symbol),
                 })
                 .collect(),
-        }
+        )
     }
 }
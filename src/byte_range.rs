@@ -0,0 +1,246 @@
+use std::{
+    any::Any,
+    fmt::{self, Display},
+};
+
+use regex_syntax::hir::{Class, ClassBytes, ClassBytesRange, Hir};
+
+use crate::*;
+
+/// Symbol matching any byte in `start..=end`
+///
+/// Automata over byte ranges are much smaller than their byte-per-link
+/// equivalents, but determinization compares symbols for equality: ranges
+/// leaving a same state must either be equal or disjoint. Automata built by
+/// [hir_to_range_nfa] satisfy this, others can be fixed with
+/// [split_ranges](Automata::split_ranges). Operations on two automata, such as
+/// [intersect](Automata::intersect) or [equivalent], first align their ranges
+/// with [align_ranges](Automata::align_ranges).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ByteRange {
+    pub start: u8,
+    pub end: u8,
+}
+
+impl ByteRange {
+    pub fn new(start: u8, end: u8) -> Self {
+        assert!(start <= end, "ByteRange start should not exceed its end");
+        Self { start, end }
+    }
+
+    pub fn contains(&self, byte: u8) -> bool {
+        self.start <= byte && byte <= self.end
+    }
+}
+
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start.escape_ascii())
+        } else {
+            write!(
+                f,
+                "{}-{}",
+                self.start.escape_ascii(),
+                self.end.escape_ascii()
+            )
+        }
+    }
+}
+
+impl IntoHir for ByteRange {
+    fn into_hir(self) -> Hir {
+        Hir::class(Class::Bytes(ClassBytes::new([ClassBytesRange::new(
+            self.start, self.end,
+        )])))
+    }
+}
+
+/// Symbol produced when compiling REGEX to NFA: single bytes or byte ranges
+pub trait ByteSymbol: Sized {
+    /// Symbols covering exactly the bytes of `start..=end`
+    fn from_range(start: u8, end: u8) -> impl Iterator<Item = Self>;
+
+    /// First and last bytes covered by this symbol
+    fn bounds(&self) -> (u8, u8);
+}
+
+impl ByteSymbol for u8 {
+    fn from_range(start: u8, end: u8) -> impl Iterator<Item = Self> {
+        start..=end
+    }
+
+    fn bounds(&self) -> (u8, u8) {
+        (*self, *self)
+    }
+}
+
+impl ByteSymbol for ByteRange {
+    fn from_range(start: u8, end: u8) -> impl Iterator<Item = Self> {
+        std::iter::once(ByteRange::new(start, end))
+    }
+
+    fn bounds(&self) -> (u8, u8) {
+        (self.start, self.end)
+    }
+}
+
+/// Partition of the byte domain in which every range of an automaton is a
+/// union of consecutive classes
+struct ByteClasses {
+    /// `boundaries[b]` is set when a class starts at byte `b`
+    boundaries: [bool; 256],
+}
+
+impl ByteClasses {
+    /// Create the classes from the bounds of the ranges
+    fn new(ranges: impl Iterator<Item = (u8, u8)>) -> Self {
+        let mut boundaries = [false; 256];
+        boundaries[0] = true;
+        for (start, end) in ranges {
+            boundaries[start as usize] = true;
+            if let Some(next) = end.checked_add(1) {
+                boundaries[next as usize] = true;
+            }
+        }
+        Self { boundaries }
+    }
+
+    /// Split `range` into the classes it covers
+    fn split(&self, range: ByteRange) -> impl Iterator<Item = ByteRange> + '_ {
+        let mut start = Some(range.start);
+        std::iter::from_fn(move || {
+            let class_start = start?;
+            let mut end = class_start;
+            while end < range.end && !self.boundaries[end as usize + 1] {
+                end += 1;
+            }
+            start = if end < range.end { Some(end + 1) } else { None };
+            Some(ByteRange::new(class_start, end))
+        })
+    }
+}
+
+impl Nfa<ByteRange> {
+    /// Split overlapping ranges into disjoint ones
+    ///
+    /// Afterwards, any two ranges of the NFA are either equal or disjoint.
+    pub fn split_ranges(&mut self) {
+        let classes = ByteClasses::new(self.range_bounds());
+        self.split_onto(&classes);
+    }
+
+    /// Split the ranges of both NFAs onto shared classes
+    ///
+    /// Afterwards, any two ranges of either NFA are either equal or disjoint.
+    pub fn align_ranges(&mut self, other: &mut Self) {
+        let classes = ByteClasses::new(self.range_bounds().chain(other.range_bounds()));
+        self.split_onto(&classes);
+        other.split_onto(&classes);
+    }
+
+    fn range_bounds(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.links().filter_map(|link| match &link.symbol {
+            MaybeSymbol::Symbol(range) => Some(range.bounds()),
+            MaybeSymbol::Epsilon => None,
+        })
+    }
+
+    fn split_onto(&mut self, classes: &ByteClasses) {
+        use crate::MaybeSymbol::*;
+
+        for link in self.take_links() {
+            match link.symbol {
+                Symbol(range) => {
+                    for class in classes.split(range) {
+                        self.link(link.from, link.to, Symbol(class));
+                    }
+                }
+                Epsilon => self.link(link.from, link.to, Epsilon),
+            }
+        }
+    }
+}
+
+impl Dfa<ByteRange> {
+    /// Split overlapping ranges into disjoint ones
+    ///
+    /// Afterwards, any two ranges of the DFA are either equal or disjoint.
+    pub fn split_ranges(&mut self) {
+        let classes = ByteClasses::new(self.range_bounds());
+        self.split_onto(&classes);
+    }
+
+    /// Split the ranges of both DFAs onto shared classes
+    ///
+    /// Afterwards, any two ranges of either DFA are either equal or disjoint.
+    pub fn align_ranges(&mut self, other: &mut Self) {
+        let classes = ByteClasses::new(self.range_bounds().chain(other.range_bounds()));
+        self.split_onto(&classes);
+        other.split_onto(&classes);
+    }
+
+    fn range_bounds(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.links().map(|link| link.symbol.bounds())
+    }
+
+    fn split_onto(&mut self, classes: &ByteClasses) {
+        for link in self.take_links() {
+            for class in classes.split(link.symbol) {
+                self.link(link.from, link.to, class);
+            }
+        }
+    }
+
+    /// Merge adjacent ranges linking the same states
+    pub fn merge_ranges(&mut self) {
+        let mut links = self.take_links();
+        links.sort_by_key(|link| (link.from, link.to, link.symbol));
+
+        let mut merged: Vec<DfaLink<ByteRange>> = Vec::with_capacity(links.len());
+        for link in links {
+            if let Some(last) = merged.last_mut()
+                && last.from == link.from
+                && last.to == link.to
+                && last.symbol.end.checked_add(1) == Some(link.symbol.start)
+            {
+                last.symbol.end = link.symbol.end;
+                continue;
+            }
+            merged.push(link);
+        }
+
+        for link in merged {
+            self.link(link.from, link.to, link.symbol);
+        }
+    }
+}
+
+/// Copies of `left` and `right` with aligned ranges when they are automata
+/// over byte ranges, see [align_ranges](Automata::align_ranges)
+///
+/// Lets the operations generic over symbols compare ranges by equality.
+pub(crate) fn aligned_ranges<T: Clone + 'static>(
+    left: &Automata<T>,
+    right: &Automata<T>,
+) -> Option<(Automata<T>, Automata<T>)> {
+    aligned_as(left, right, Dfa::<ByteRange>::align_ranges)
+        .or_else(|| aligned_as(left, right, Nfa::<ByteRange>::align_ranges))
+}
+
+fn aligned_as<A, B>(left: &B, right: &B, align: fn(&mut A, &mut A)) -> Option<(B, B)>
+where
+    A: Clone + 'static,
+    B: 'static,
+{
+    let mut left = (left as &dyn Any).downcast_ref::<A>()?.clone();
+    let mut right = (right as &dyn Any).downcast_ref::<A>()?.clone();
+    align(&mut left, &mut right);
+
+    let cast = |automaton: A| {
+        *(Box::new(automaton) as Box<dyn Any>)
+            .downcast::<B>()
+            .unwrap()
+    };
+    Some((cast(left), cast(right)))
+}
@@ -0,0 +1,138 @@
+use rustc_hash::FxHashMap;
+
+use crate::*;
+
+impl<T: Clone> Nfa<T> {
+    /// NFA accepting only the empty string
+    pub fn empty_string() -> Self {
+        let mut nfa = Nfa::new();
+        nfa.initial_states.insert(0);
+        nfa.accept_states.insert(0);
+        nfa
+    }
+
+    /// NFA accepting the words of `self` followed by the words of `other`
+    ///
+    /// `other` is renumbered after the states of `self`, its initial states
+    /// reached from the accept states of `self` through epsilon links.
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut nfa = self.clone();
+        let mut other = other.renumbered(&self.next_counter());
+
+        for link in other.take_links() {
+            nfa.link(link.from, link.to, link.symbol);
+        }
+        for from in std::mem::take(&mut nfa.accept_states) {
+            for to in &other.initial_states {
+                nfa.link(from, *to, MaybeSymbol::Epsilon);
+            }
+        }
+        nfa.accept_states = other.accept_states;
+
+        nfa
+    }
+
+    /// NFA accepting the words of `self` or `other`
+    ///
+    /// Both NFAs are put side by side, `other` being renumbered after the
+    /// states of `self`.
+    pub fn union_nfa(&self, other: &Self) -> Self {
+        let mut nfa = self.clone();
+        let mut other = other.renumbered(&self.next_counter());
+
+        for link in other.take_links() {
+            nfa.link(link.from, link.to, link.symbol);
+        }
+        nfa.initial_states.extend(other.initial_states);
+        nfa.accept_states.extend(other.accept_states);
+
+        nfa
+    }
+
+    /// NFA accepting any number of words of `self` in a row (Kleene star)
+    pub fn star(&self) -> Self {
+        self.plus().optional()
+    }
+
+    /// NFA accepting at least one word of `self` in a row
+    ///
+    /// Accept states link back to the initial states through epsilon links.
+    pub fn plus(&self) -> Self {
+        let mut nfa = self.clone();
+        for from in &self.accept_states {
+            for to in &self.initial_states {
+                nfa.link(*from, *to, MaybeSymbol::Epsilon);
+            }
+        }
+        nfa
+    }
+
+    /// NFA accepting the words of `self` and the empty string
+    ///
+    /// A fresh state is both initial and accepting, so that no path of `self`
+    /// is changed.
+    pub fn optional(&self) -> Self {
+        let mut nfa = self.clone();
+        let state = self.next_counter().next();
+        nfa.initial_states.insert(state);
+        nfa.accept_states.insert(state);
+        nfa
+    }
+
+    /// NFA accepting from `min` to `max` words of `self` in a row, or at least
+    /// `min` when there is no `max`
+    ///
+    /// Same as `{min,max}` in a REGEX, each repetition is a renumbered copy.
+    pub fn repeat(&self, min: u32, max: Option<u32>) -> Self {
+        if let Some(max) = max {
+            assert!(
+                min <= max,
+                "Minimum repetitions should not exceed the maximum"
+            );
+        }
+
+        let mut nfa = Nfa::empty_string();
+        for _ in 0..min {
+            nfa = nfa.concat(self);
+        }
+
+        match max {
+            Some(max) => {
+                let optional = self.optional();
+                for _ in min..max {
+                    nfa = nfa.concat(&optional);
+                }
+            }
+            None => nfa = nfa.concat(&self.star()),
+        }
+
+        nfa
+    }
+
+    /// NFA accepting the mirror image of the words of `self`
+    pub fn reverse(&self) -> Self {
+        let mut nfa = self.clone();
+        nfa.invert();
+        nfa
+    }
+
+    /// Copy with states numbered by `counter`
+    fn renumbered(&self, counter: &Counter) -> Self {
+        let mut renumbered: FxHashMap<State, State> = Default::default();
+        for state in self.states() {
+            renumbered.entry(state).or_insert_with(|| counter.next());
+        }
+
+        Automata::from_parts(
+            self.initial_states.iter().map(|s| renumbered[s]).collect(),
+            self.accept_states.iter().map(|s| renumbered[s]).collect(),
+            self.links()
+                .map(|link| Link {
+                    from: renumbered[&link.from],
+                    to: renumbered[&link.to],
+                    symbol: link.symbol.clone(),
+                })
+                .collect(),
+        )
+    }
+}
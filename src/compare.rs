@@ -0,0 +1,213 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use rustc_hash::FxHashMap;
+
+use crate::{byte_range::aligned_ranges, determine::epsilon_closure, *};
+
+/// Check if two DFAs accept the same language
+///
+/// Breadth-first search of the product of the DFAs, a missing link leading to
+/// an implicit dead state. When the languages differ, returns a shortest word
+/// accepted by only one of them. Byte ranges are aligned first, see
+/// [align_ranges](Automata::align_ranges).
+pub fn equivalent<T>(left: &Dfa<T>, right: &Dfa<T>) -> Result<(), Vec<T>>
+where
+    T: Eq + Hash + Clone + 'static,
+{
+    match aligned_ranges(left, right) {
+        Some((left, right)) => equivalent_aligned(&left, &right),
+        None => equivalent_aligned(left, right),
+    }
+}
+
+fn equivalent_aligned<T>(left: &Dfa<T>, right: &Dfa<T>) -> Result<(), Vec<T>>
+where
+    T: Eq + Hash + Clone,
+{
+    type Pair = (Option<State>, Option<State>);
+
+    let initial: Pair = (Some(left.initial_state()), Some(right.initial_state()));
+    // Pair each product state was first reached from, and with which symbol
+    let mut parents: FxHashMap<Pair, Option<(Pair, &T)>> = Default::default();
+    parents.insert(initial, None);
+    let mut to_explore = VecDeque::from([initial]);
+
+    while let Some(pair @ (left_state, right_state)) = to_explore.pop_front() {
+        let left_accepts = left_state.is_some_and(|state| left.accept_states.contains(&state));
+        let right_accepts = right_state.is_some_and(|state| right.accept_states.contains(&state));
+
+        if left_accepts != right_accepts {
+            let mut word = Vec::new();
+            let mut current = pair;
+            while let Some((parent, symbol)) = parents[&current] {
+                word.push(symbol.clone());
+                current = parent;
+            }
+            word.reverse();
+            return Err(word);
+        }
+
+        // Targets by symbol, in link order to keep the search deterministic
+        let mut targets: Vec<(&T, Pair)> = Vec::new();
+        let mut positions: FxHashMap<&T, usize> = Default::default();
+        for link in left_state
+            .into_iter()
+            .flat_map(|state| left.links_from(state))
+        {
+            positions.insert(&link.symbol, targets.len());
+            targets.push((&link.symbol, (Some(link.to), None)));
+        }
+        for link in right_state
+            .into_iter()
+            .flat_map(|state| right.links_from(state))
+        {
+            match positions.get(&link.symbol) {
+                Some(position) => targets[*position].1.1 = Some(link.to),
+                None => targets.push((&link.symbol, (None, Some(link.to)))),
+            }
+        }
+
+        for (symbol, to) in targets {
+            if parents.contains_key(&to) {
+                continue;
+            }
+            parents.insert(to, Some((pair, symbol)));
+            to_explore.push_back(to);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if every word accepted by `left` is accepted by `right`
+///
+/// Antichain-based search: `left` is explored state by state, paired with the
+/// set of states `right` can be in after the same word, so that `right` is
+/// never fully determinized. A pair is skipped when a pair with the same
+/// `left` state and a subset of its `right` states was already seen, as any
+/// witness found from the former can be found from the latter. Returns a
+/// shortest word accepted by `left` but not by `right` otherwise. Byte
+/// ranges are aligned first, see [align_ranges](Automata::align_ranges).
+pub fn is_subset<T>(left: &Nfa<T>, right: &Nfa<T>) -> Result<(), Vec<T>>
+where
+    T: Eq + Hash + Clone + 'static,
+{
+    match aligned_ranges(left, right) {
+        Some((left, right)) => is_subset_aligned(&left, &right),
+        None => is_subset_aligned(left, right),
+    }
+}
+
+fn is_subset_aligned<T>(left: &Nfa<T>, right: &Nfa<T>) -> Result<(), Vec<T>>
+where
+    T: Eq + Hash + Clone,
+{
+    let mut search: Antichain<T> = Antichain::default();
+
+    let right_initial = epsilon_closure(right, right.initial_states.iter().copied());
+    for state in epsilon_closure(left, left.initial_states.iter().copied()) {
+        search.push(state, right_initial.clone(), None);
+    }
+
+    while let Some(index) = search.to_explore.pop_front() {
+        let nodes = &search.nodes;
+        let node = &nodes[index];
+        let accepted_by_right = node
+            .right_states
+            .iter()
+            .any(|state| right.accept_states.contains(state));
+
+        if left.accept_states.contains(&node.state) && !accepted_by_right {
+            let mut word = Vec::new();
+            let mut current = index;
+            while let Some((parent, symbol)) = nodes[current].parent {
+                word.push(symbol.clone());
+                current = parent;
+            }
+            word.reverse();
+            return Err(word);
+        }
+
+        let mut right_targets: FxHashMap<&T, Vec<State>> = Default::default();
+        for state in &node.right_states {
+            for link in right.links_from(*state) {
+                if let MaybeSymbol::Symbol(symbol) = &link.symbol {
+                    right_targets.entry(symbol).or_default().push(link.to);
+                }
+            }
+        }
+
+        let mut successors = Vec::new();
+        for link in left.links_from(node.state) {
+            let MaybeSymbol::Symbol(symbol) = &link.symbol else {
+                continue;
+            };
+            let right_to = right_targets.get(symbol).cloned().unwrap_or_default();
+            let right_to = epsilon_closure(right, right_to);
+            for to in epsilon_closure(left, [link.to]) {
+                successors.push((to, right_to.clone(), symbol));
+            }
+        }
+
+        for (to, right_to, symbol) in successors {
+            search.push(to, right_to, Some((index, symbol)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Pairs explored by [is_subset]
+struct Antichain<'a, T> {
+    nodes: Vec<Node<'a, T>>,
+    /// Nodes kept for each `left` state
+    kept: FxHashMap<State, Vec<usize>>,
+    to_explore: VecDeque<usize>,
+}
+
+struct Node<'a, T> {
+    state: State,
+    /// Sorted states of `right`
+    right_states: Vec<State>,
+    /// Node this one was reached from, and with which symbol
+    parent: Option<(usize, &'a T)>,
+}
+
+impl<T> Default for Antichain<'_, T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            kept: Default::default(),
+            to_explore: VecDeque::new(),
+        }
+    }
+}
+
+impl<'a, T> Antichain<'a, T> {
+    /// Queue the pair unless it is subsumed by a kept one
+    fn push(&mut self, state: State, right_states: Vec<State>, parent: Option<(usize, &'a T)>) {
+        let kept = self.kept.entry(state).or_default();
+        let subsumed = kept
+            .iter()
+            .any(|index| is_sorted_subset(&self.nodes[*index].right_states, &right_states));
+        if subsumed {
+            return;
+        }
+
+        kept.push(self.nodes.len());
+        self.to_explore.push_back(self.nodes.len());
+        self.nodes.push(Node {
+            state,
+            right_states,
+            parent,
+        });
+    }
+}
+
+/// Check if every item of the sorted `small` is in the sorted `large`
+fn is_sorted_subset(small: &[State], large: &[State]) -> bool {
+    let mut large = large.iter();
+    small
+        .iter()
+        .all(|item| large.by_ref().any(|other| other == item))
+}
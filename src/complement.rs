@@ -0,0 +1,70 @@
+use std::hash::Hash;
+
+use rustc_hash::FxHashSet;
+
+use crate::*;
+
+impl<T> Dfa<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Make the transition function total over `alphabet`
+    ///
+    /// Missing transitions go to a new dead state, looping on every symbol.
+    /// Nothing is added when the DFA is already complete. Symbols of the DFA
+    /// should all be part of `alphabet`.
+    pub fn complete(&mut self, alphabet: &[T]) {
+        let counter = self.next_counter();
+        let mut dead = None;
+
+        for state in self.states_set() {
+            let defined: FxHashSet<&T> = self.links_from(state).map(|link| &link.symbol).collect();
+            let missing: Vec<T> = alphabet
+                .iter()
+                .filter(|symbol| !defined.contains(symbol))
+                .cloned()
+                .collect();
+
+            for symbol in missing {
+                let to = *dead.get_or_insert_with(|| counter.next());
+                self.link(state, to, symbol);
+            }
+        }
+
+        if let Some(dead) = dead {
+            for symbol in alphabet {
+                self.link(dead, dead, symbol.clone());
+            }
+        }
+    }
+
+    /// Minimal DFA accepting the words over `alphabet` not accepted by `self`
+    pub fn complement(&self, alphabet: &[T]) -> Dfa<T> {
+        let mut complement = self.clone();
+        complement.complete(alphabet);
+
+        let accept_states = complement
+            .states()
+            .filter(|state| !self.accept_states.contains(state))
+            .collect();
+        complement.accept_states = accept_states;
+
+        minimize_hopcroft(complement)
+    }
+}
+
+impl Dfa<u8> {
+    /// Same as [Dfa::complete], over every byte
+    pub fn complete_bytes(&mut self) {
+        self.complete(&all_bytes());
+    }
+
+    /// Same as [Dfa::complement], over every byte
+    pub fn complement_bytes(&self) -> Dfa<u8> {
+        self.complement(&all_bytes())
+    }
+}
+
+fn all_bytes() -> Vec<u8> {
+    (u8::MIN..=u8::MAX).collect()
+}
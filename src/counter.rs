@@ -14,8 +14,13 @@ impl Counter {
     }
 
     pub fn next(&self) -> State {
+        self.try_next().expect("State overflow")
+    }
+
+    /// Same as [Counter::next], returning `None` on state overflow
+    pub fn try_next(&self) -> Option<State> {
         let next = self.next.get();
-        self.next.set(next.checked_add(1).expect("State overflow"));
-        next
+        self.next.set(next.checked_add(1)?);
+        Some(next)
     }
 }
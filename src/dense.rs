@@ -0,0 +1,169 @@
+use rustc_hash::FxHashMap;
+
+use crate::*;
+
+/// DFA compiled into a transition table, running in O(1) per byte
+///
+/// Bytes with the same transitions everywhere share an equivalence class, each
+/// state owns a row of the table indexed by class. State `0` is the dead
+/// state: it is never accepting and loops on itself. Every state of the DFA
+/// no accept state is reachable from, such as the sink added by
+/// [complete](Automata::complete), is compiled to it.
+#[derive(Debug, Clone)]
+pub struct DenseDfa {
+    /// Equivalence class of each byte
+    classes: [u8; 256],
+    /// Number of classes, the length of a row
+    stride: usize,
+    /// `table[state * stride + class]` is the next state
+    table: Vec<State>,
+    accept: Vec<bool>,
+    initial: State,
+}
+
+impl DenseDfa {
+    pub const DEAD: State = 0;
+
+    /// Compile a DFA over bytes or byte ranges
+    ///
+    /// Links leaving a same state should not overlap.
+    pub fn new<S: ByteSymbol>(dfa: &Dfa<S>) -> Self {
+        let counter = Counter::new(Self::DEAD + 1);
+        let live = dfa.coreachable_states();
+        let mut dense_states: FxHashMap<State, State> = Default::default();
+        for state in dfa.states() {
+            dense_states.entry(state).or_insert_with(|| {
+                if live.contains(&state) {
+                    counter.next()
+                } else {
+                    Self::DEAD
+                }
+            });
+        }
+
+        // Full rows of every state, one column per byte
+        let len = live.len() + 1;
+        let mut rows = vec![[Self::DEAD; 256]; len];
+        for link in dfa.links() {
+            let from = dense_states[&link.from];
+            if from == Self::DEAD {
+                continue;
+            }
+            let row = &mut rows[from as usize];
+            let (start, end) = link.symbol.bounds();
+            for byte in start..=end {
+                row[byte as usize] = dense_states[&link.to];
+            }
+        }
+
+        // Refine byte classes until bytes of a same class agree on every row
+        let mut classes = [0u8; 256];
+        let mut stride = 1;
+        for row in &rows {
+            let mut refined: FxHashMap<(u8, State), u8> = Default::default();
+            for byte in 0..256 {
+                let next = refined.len() as u8;
+                classes[byte] = *refined.entry((classes[byte], row[byte])).or_insert(next);
+            }
+            stride = refined.len();
+        }
+
+        let mut table = vec![Self::DEAD; len * stride];
+        for (state, row) in rows.iter().enumerate() {
+            for byte in 0..256 {
+                table[state * stride + classes[byte] as usize] = row[byte];
+            }
+        }
+
+        let mut accept = vec![false; len];
+        for state in &dfa.accept_states {
+            accept[dense_states[state] as usize] = true;
+        }
+
+        Self {
+            classes,
+            stride,
+            table,
+            accept,
+            initial: dense_states[&dfa.initial_state()],
+        }
+    }
+
+    pub fn initial_state(&self) -> State {
+        self.initial
+    }
+
+    pub fn next_state(&self, state: State, byte: u8) -> State {
+        self.table[state as usize * self.stride + self.classes[byte as usize] as usize]
+    }
+
+    pub fn is_accept(&self, state: State) -> bool {
+        self.accept[state as usize]
+    }
+
+    pub fn is_dead(&self, state: State) -> bool {
+        state == Self::DEAD
+    }
+
+    /// Number of byte equivalence classes
+    pub fn class_count(&self) -> usize {
+        self.stride
+    }
+
+    /// Same as [execute_dfa]: end of the shortest prefix of `haystack` accepted
+    pub fn execute(&self, haystack: &[u8]) -> Option<usize> {
+        self.execute_with(haystack, MatchKind::Earliest)
+    }
+
+    /// Same as [execute_dfa_with]: end of the prefix selected by `kind`
+    pub fn execute_with(&self, haystack: &[u8], kind: MatchKind) -> Option<usize> {
+        let mut state = self.initial;
+        let mut last_match = None;
+
+        for (idx, byte) in haystack.iter().enumerate() {
+            if self.is_accept(state) {
+                if kind == MatchKind::Earliest {
+                    return Some(idx);
+                }
+                last_match = Some(idx);
+            }
+
+            state = self.next_state(state, *byte);
+            if self.is_dead(state) {
+                return last_match;
+            }
+        }
+
+        if self.is_accept(state) {
+            Some(haystack.len())
+        } else {
+            last_match
+        }
+    }
+
+    /// Check if the whole `haystack` is accepted
+    pub fn accepts(&self, haystack: &[u8]) -> bool {
+        let mut state = self.initial;
+
+        for byte in haystack {
+            state = self.next_state(state, *byte);
+            if self.is_dead(state) {
+                return false;
+            }
+        }
+
+        self.is_accept(state)
+    }
+}
+
+impl From<&Dfa<u8>> for DenseDfa {
+    fn from(dfa: &Dfa<u8>) -> Self {
+        DenseDfa::new(dfa)
+    }
+}
+
+impl From<&Dfa<ByteRange>> for DenseDfa {
+    fn from(dfa: &Dfa<ByteRange>) -> Self {
+        DenseDfa::new(dfa)
+    }
+}
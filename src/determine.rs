@@ -8,19 +8,61 @@ use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::*;
 
+/// DFA minimization algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Minimization {
+    /// Double reversal, simple but the intermediate DFA may be exponentially
+    /// larger than the minimal one
+    #[default]
+    Brzozowski,
+    /// Subset construction followed by partition refinement, see [minimize_hopcroft]
+    Hopcroft,
+}
+
 /// Create minimal DFA from NFA using Brzozowski's algorithm
-pub fn determine_and_minimize_nfa<T>(mut nfa: Nfa<T>) -> Dfa<T>
+pub fn determine_and_minimize_nfa<T>(nfa: Nfa<T>) -> Dfa<T>
+where
+    T: Eq + Hash + Clone + Debug,
+{
+    determine_and_minimize_nfa_with(nfa, Minimization::Brzozowski)
+}
+
+/// Create minimal DFA from NFA using the given minimization algorithm
+pub fn determine_and_minimize_nfa_with<T>(mut nfa: Nfa<T>, minimization: Minimization) -> Dfa<T>
+where
+    T: Eq + Hash + Clone + Debug,
+{
+    match minimization {
+        Minimization::Brzozowski => {
+            nfa.invert();
+            let mut dfa = determine_nfa(&nfa);
+            dfa.invert();
+            let nfa2 = dfa.into_nfa();
+            determine_nfa(&nfa2)
+        }
+        Minimization::Hopcroft => minimize_hopcroft(determine_nfa(&nfa)),
+    }
+}
+
+/// Create DFA from NFA using the subset construction, without minimization
+pub fn determine_nfa<T>(nfa: &Nfa<T>) -> Dfa<T>
 where
     T: Eq + Hash + Clone + Debug,
 {
-    nfa.invert();
-    let mut dfa = determine_nfa(&nfa);
-    dfa.invert();
-    let nfa2 = dfa.into_nfa();
-    determine_nfa(&nfa2)
+    determinize(nfa).dfa
 }
 
-fn determine_nfa<T>(nfa: &Nfa<T>) -> Dfa<T>
+/// DFA built by the subset construction, along with its NFA origin
+#[derive(Debug, Clone)]
+pub struct Determinized<T> {
+    pub dfa: Dfa<T>,
+    /// NFA states making up each DFA state, sorted and closed over epsilon links
+    pub nfa_states: FxHashMap<State, Vec<State>>,
+}
+
+/// Create DFA from NFA using the subset construction, recording which NFA
+/// states make up each DFA state
+pub fn determinize<T>(nfa: &Nfa<T>) -> Determinized<T>
 where
     T: Eq + Hash + Clone + Debug,
 {
@@ -60,11 +102,7 @@ where
         for from in std::mem::take(&mut to_explore) {
             let mut to_by_symbol: FxHashMap<T, MultiState> = FxHashMap::default();
 
-            for link in &nfa.links {
-                if !from.contains(&link.from) {
-                    continue;
-                }
-
+            for link in from.iter().flat_map(|state| nfa.links_from(*state)) {
                 let MaybeSymbol::Symbol(symbol) = &link.symbol else {
                     continue;
                 };
@@ -92,44 +130,165 @@ where
         }
     }
 
-    dfa
+    let nfa_states = state_mapping
+        .into_iter()
+        .map(|(multi_state, state)| (state, multi_state.0))
+        .collect();
+
+    Determinized { dfa, nfa_states }
+}
+
+/// Create DFA from NFA following the priority of its links, for
+/// leftmost-first execution
+///
+/// Links leaving a same state are tried in the order they were added, as a
+/// backtracking engine would: [hir_to_nfa] adds them following the order of
+/// alternations and the greedy or lazy flag of repetitions. NFA states with a
+/// lower priority than an accept state are dropped, so the DFA only accepts
+/// the prefixes a backtracking engine could report. Initial states are tried
+/// in increasing order.
+///
+/// The longest of these prefixes is the one a backtracking engine reports:
+/// run the DFA with [MatchKind::LeftmostLongest].
+pub fn determine_nfa_leftmost_first<T>(nfa: &Nfa<T>) -> Dfa<T>
+where
+    T: Eq + Hash + Clone + Debug,
+{
+    determinize_leftmost_first(nfa).dfa
+}
+
+/// Same as [determine_nfa_leftmost_first], recording which NFA states make up
+/// each DFA state
+///
+/// The NFA states are sorted, they are only closed over epsilon links up to
+/// the first accept state.
+pub fn determinize_leftmost_first<T>(nfa: &Nfa<T>) -> Determinized<T>
+where
+    T: Eq + Hash + Clone + Debug,
+{
+    let mut dfa = Dfa::new();
+    let counter = Counter::new(0);
+    let mut state_mapping: FxHashMap<Vec<State>, State> = Default::default();
+    let mut to_explore = Vec::new();
+
+    let mut dfa_state = |ordered: Vec<State>, dfa: &mut Dfa<T>, to_explore: &mut Vec<_>| {
+        if let Some(state) = state_mapping.get(&ordered) {
+            return *state;
+        }
+        let state = counter.next();
+        if ordered.iter().any(|s| nfa.accept_states.contains(s)) {
+            dfa.accept_states.insert(state);
+        }
+        state_mapping.insert(ordered.clone(), state);
+        to_explore.push((state, ordered));
+        state
+    };
+
+    let mut initial_states: Vec<State> = nfa.initial_states.iter().copied().collect();
+    initial_states.sort();
+    let initial_state = dfa_state(
+        ordered_closure(nfa, &initial_states),
+        &mut dfa,
+        &mut to_explore,
+    );
+    dfa.initial_states.insert(initial_state);
+
+    while let Some((from, ordered)) = to_explore.pop() {
+        let mut symbols = Vec::new();
+        let mut to_by_symbol: FxHashMap<T, Vec<State>> = Default::default();
+
+        for link in ordered.iter().flat_map(|state| nfa.links_from(*state)) {
+            let MaybeSymbol::Symbol(symbol) = &link.symbol else {
+                continue;
+            };
+
+            to_by_symbol
+                .entry(symbol.clone())
+                .or_insert_with(|| {
+                    symbols.push(symbol.clone());
+                    Vec::new()
+                })
+                .push(link.to);
+        }
+
+        for symbol in symbols {
+            let to = ordered_closure(nfa, &to_by_symbol[&symbol]);
+            let to = dfa_state(to, &mut dfa, &mut to_explore);
+            dfa.link(from, to, symbol);
+        }
+    }
+
+    let nfa_states = state_mapping
+        .into_iter()
+        .map(|(mut ordered, state)| {
+            ordered.sort();
+            (state, ordered)
+        })
+        .collect();
+
+    Determinized { dfa, nfa_states }
+}
+
+/// Follow epsilon links depth first, in priority order, up to the first
+/// accept state
+fn ordered_closure<T>(nfa: &Nfa<T>, from: &[State]) -> Vec<State> {
+    let mut to_explore: Vec<State> = from.iter().rev().copied().collect();
+    let mut explored = FxHashSet::default();
+    let mut result = Vec::new();
+
+    while let Some(state) = to_explore.pop() {
+        if !explored.insert(state) {
+            continue;
+        }
+        result.push(state);
+        if nfa.accept_states.contains(&state) {
+            break;
+        }
+
+        let epsilons: Vec<State> = nfa
+            .links_from(state)
+            .filter(|link| matches!(link.symbol, MaybeSymbol::Epsilon))
+            .map(|link| link.to)
+            .collect();
+        to_explore.extend(epsilons.into_iter().rev());
+    }
+
+    result
 }
 
 /// Normalize NFA multi state by following recursively epsilon links
 fn normalize_multi_state<T>(nfa: &Nfa<T>, from: MultiState) -> MultiState {
-    let mut to_explore = from.0;
+    MultiState(epsilon_closure(nfa, from.0))
+}
+
+/// States reachable from `from` through epsilon links, sorted
+pub(crate) fn epsilon_closure<T>(
+    nfa: &Nfa<T>,
+    from: impl IntoIterator<Item = State>,
+) -> Vec<State> {
+    let mut to_explore: Vec<State> = from.into_iter().collect();
     let mut explored = FxHashSet::default();
-    let mut result = MultiState::default();
 
-    while !to_explore.is_empty() {
-        for state in std::mem::take(&mut to_explore) {
-            if explored.contains(&state) {
-                continue;
-            }
-            result.push(state);
-            explored.insert(state);
-            for link in nfa.links_from(state) {
-                if let MaybeSymbol::Epsilon = &link.symbol {
-                    to_explore.push(link.to);
-                }
+    while let Some(state) = to_explore.pop() {
+        if !explored.insert(state) {
+            continue;
+        }
+        for link in nfa.links_from(state) {
+            if let MaybeSymbol::Epsilon = link.symbol {
+                to_explore.push(link.to);
             }
         }
     }
 
-    result.sort();
-    result
+    let mut closure: Vec<State> = explored.into_iter().collect();
+    closure.sort();
+    closure
 }
 
 /// A list of states, must be sorted before calling Eq or Hash
 #[derive(Clone, Default)]
 struct MultiState(Vec<State>);
 
-impl MultiState {
-    pub fn sort(&mut self) {
-        self.0.sort();
-    }
-}
-
 impl Hash for MultiState {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         assert!(self.0.is_sorted(), "MultiState is not sorted on hash");
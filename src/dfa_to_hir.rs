@@ -36,14 +36,21 @@ impl IntoHir for bool {
 }
 
 /// Convert DFA back to REGEX Syntax
-pub fn dfa_to_hir<T: IntoHir>(dfa_origin: Dfa<T>) -> Hir {
+///
+/// The DFA is trimmed first, dead branches don't show up in the REGEX. A DFA
+/// of the empty language gives [Hir::fail].
+pub fn dfa_to_hir<T: IntoHir>(mut dfa_origin: Dfa<T>) -> Hir {
+    dfa_origin.trim();
+    if dfa_origin.accept_states.is_empty() {
+        return Hir::fail();
+    }
     let counter = dfa_origin.next_counter();
 
-    let mut dfa = Dfa {
-        accept_states: Default::default(),
-        initial_states: Default::default(),
-        links: dfa_origin
-            .links
+    let mut dfa = Dfa::from_parts(
+        Default::default(),
+        Default::default(),
+        dfa_origin
+            .take_links()
             .into_iter()
             .map(|link| Link {
                 from: link.from,
@@ -51,7 +58,7 @@ pub fn dfa_to_hir<T: IntoHir>(dfa_origin: Dfa<T>) -> Hir {
                 symbol: link.symbol.into_hir(),
             })
             .collect(),
-    };
+    );
 
     let start = counter.next();
     let end = counter.next();
@@ -125,47 +132,73 @@ pub fn dfa_to_hir<T: IntoHir>(dfa_origin: Dfa<T>) -> Hir {
                     vec![in_sym, out_sym]
                 };
 
-                dfa.link(*from, *to, Hir::concat(items));
+                link_merged(&mut dfa, *from, *to, Hir::concat(items));
             }
         }
 
-        merge_sibling_edges(&mut dfa);
-
         debug_assert_eq!(dfa.links_from_to(rip, rip).count(), 0);
     }
 
-    merge_sibling_edges(&mut dfa);
+    let mut links = dfa.into_links();
+    assert_eq!(links.len(), 1);
 
-    assert_eq!(dfa.links.len(), 1);
-
-    dfa.links.remove(0).symbol
+    links.remove(0).symbol
 }
 
-fn merge_sibling_edges(dfa: &mut Automata<Hir>) {
-    let states = dfa.states_set();
+/// Link `from` to `to`, merged with the link already between them unless it
+/// is a self loop
+fn link_merged(dfa: &mut Automata<Hir>, from: State, to: State, hir: Hir) {
+    if from == to {
+        dfa.link(from, to, hir);
+        return;
+    }
 
-    for from in &states {
-        for to in &states {
-            if from == to {
-                continue;
-            }
+    let existing = dfa.links_from_to(from, to).next().map(|l| l.symbol.clone());
+    match existing {
+        Some(existing) if existing == hir => {}
+        Some(existing) => {
+            let merged = Hir::alternation(vec![existing, hir]);
+            dfa.remove_links(from, to);
+            dfa.link(from, to, merged);
+        }
+        None => dfa.link(from, to, hir),
+    }
+}
 
-            let zom = dfa
-                .links_from_to(*from, *to)
-                .map(|l| l.symbol.clone())
-                .zero_one_or_many_unique();
+fn merge_sibling_edges(dfa: &mut Automata<Hir>) {
+    // Merged links are appended pair by pair, following the iteration order of
+    // the states set, self loops are kept first
+    let rank: FxHashMap<State, usize> = dfa
+        .states_set()
+        .into_iter()
+        .enumerate()
+        .map(|(rank, state)| (state, rank))
+        .collect();
+
+    let mut siblings = FxHashMap::<_, Vec<_>>::default();
+    for link in dfa.take_links() {
+        if link.from == link.to {
+            dfa.link(link.from, link.to, link.symbol);
+        } else {
+            siblings
+                .entry((link.from, link.to))
+                .or_default()
+                .push(link.symbol);
+        }
+    }
 
-            dfa.remove_links(*from, *to);
+    let mut siblings: Vec<_> = siblings.into_iter().collect();
+    siblings.sort_by_key(|((from, to), _)| (rank[from], rank[to]));
 
-            match zom {
-                ZeroOneOrMany::Many(hirs) => {
-                    dfa.link(*from, *to, Hir::alternation(hirs));
-                }
-                ZeroOneOrMany::One(hir) => {
-                    dfa.link(*from, *to, hir);
-                }
-                _ => {}
+    for ((from, to), hirs) in siblings {
+        match hirs.into_iter().zero_one_or_many_unique() {
+            ZeroOneOrMany::Many(hirs) => {
+                dfa.link(from, to, Hir::alternation(hirs));
+            }
+            ZeroOneOrMany::One(hir) => {
+                dfa.link(from, to, hir);
             }
+            _ => {}
         }
     }
 }
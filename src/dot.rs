@@ -22,7 +22,7 @@ where
             writeln!(out, "s{accept} [label=\"accept_{accept}\"]")?;
         }
 
-        for link in &self.links {
+        for link in self.links() {
             let Link { from, symbol, to } = link;
             writeln!(out, "s{from} -> s{to} [label=\"{symbol}\"]")?;
         }
@@ -0,0 +1,63 @@
+use std::fmt::{self, Display};
+
+use regex_syntax::{ast, hir::Look};
+
+/// Error returned when a REGEX can't be converted to an automaton
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The pattern could not be parsed
+    Syntax(Box<regex_syntax::Error>),
+    /// The pattern uses a construct that can't be compiled
+    ///
+    /// `span` locates the construct in the pattern, it is only known when the
+    /// error comes from a parsed pattern.
+    Unsupported {
+        syntax: UnsupportedSyntax,
+        span: Option<ast::Span>,
+    },
+    /// The automaton needs more states than [State](crate::State) can represent
+    StateOverflow,
+    /// The automaton exceeds the configured size limit
+    SizeLimitExceeded { limit: usize },
+}
+
+/// Syntax accepted by `regex_syntax` that can't be compiled to an automaton
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedSyntax {
+    UnicodeWordBoundary(Look),
+    /// Look-around assertion checked at the end of a match, allowed by
+    /// [Config::whole_input](crate::Config::whole_input)
+    LookAtMatchEnd,
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Syntax(err) => Display::fmt(err, f),
+            BuildError::Unsupported { syntax, span } => {
+                write!(f, "{syntax} is not supported")?;
+                if let Some(span) = span {
+                    write!(f, " at {}..{}", span.start.offset, span.end.offset)?;
+                }
+                Ok(())
+            }
+            BuildError::StateOverflow => f.write_str("State overflow"),
+            BuildError::SizeLimitExceeded { limit } => {
+                write!(f, "automaton exceeds the size limit of {limit} links")
+            }
+        }
+    }
+}
+
+impl Display for UnsupportedSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsupportedSyntax::UnicodeWordBoundary(_) => f.write_str("Unicode word boundary"),
+            UnsupportedSyntax::LookAtMatchEnd => {
+                f.write_str("look-around assertion at the end of a prefix match")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
@@ -1,26 +1,55 @@
-use crate::Dfa;
+use crate::{ByteRange, Dfa, Matcher};
 
-/// Not optimized DFA executor for testing
-pub fn execute_dfa<T>(dfa: &Dfa<T>, haystack: &[T]) -> Option<usize>
-where
-    T: Eq,
-{
-    let mut state = dfa.initial_state();
-
-    'next_item: for (idx, item) in haystack.iter().enumerate() {
-        if dfa.accept_states.contains(&state) {
-            return Some(idx);
-        }
+/// Symbol of an automaton that can be matched against haystack items
+pub trait Matches<I> {
+    fn matches(&self, item: &I) -> bool;
+}
 
-        for link in dfa.links_from(state) {
-            if &link.symbol == item {
-                state = link.to;
-                continue 'next_item;
-            }
-        }
+impl<T: Eq> Matches<T> for T {
+    fn matches(&self, item: &T) -> bool {
+        self == item
+    }
+}
 
-        return None;
+impl Matches<u8> for ByteRange {
+    fn matches(&self, item: &u8) -> bool {
+        self.contains(*item)
     }
+}
 
-    dfa.accept_states.contains(&state).then_some(haystack.len())
+/// Which match the executor reports among the accepted prefixes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKind {
+    /// The longest accepted prefix (POSIX)
+    ///
+    /// On a DFA built with
+    /// [determine_nfa_leftmost_first](crate::determine_nfa_leftmost_first),
+    /// the prefix a backtracking engine would report (Perl).
+    LeftmostLongest,
+    /// The shortest accepted prefix, execution stops as soon as it is found
+    #[default]
+    Earliest,
+}
+
+/// Not optimized DFA executor for testing
+///
+/// Returns the end of the shortest prefix of `haystack` accepted by `dfa`.
+/// Look-around assertions see this prefix as the whole input, so a DFA built
+/// with [Config::whole_input](crate::Config::whole_input) matches `$` at its
+/// end whatever follows.
+pub fn execute_dfa<T, I>(dfa: &Dfa<T>, haystack: &[I]) -> Option<usize>
+where
+    T: Matches<I>,
+{
+    execute_dfa_with(dfa, haystack, MatchKind::Earliest)
+}
+
+/// Same as [execute_dfa], reporting the prefix selected by `kind`
+pub fn execute_dfa_with<T, I>(dfa: &Dfa<T>, haystack: &[I], kind: MatchKind) -> Option<usize>
+where
+    T: Matches<I>,
+{
+    let mut matcher = Matcher::without_live_states(dfa, kind);
+    matcher.feed(haystack);
+    matcher.finish()
 }
@@ -1,15 +1,221 @@
-use regex_syntax::hir::{Class, Hir, HirKind, Literal};
+use regex_syntax::{
+    ast::{self, AssertionKind, Ast, Span},
+    hir::{Class, Hir, HirKind, Literal, Look},
+    utf8::Utf8Sequences,
+};
+use rustc_hash::FxHashMap;
 
-use crate::*;
+use crate::{
+    look::{Margins, is_unicode_word_look, resolve_looks, resolve_search_looks},
+    *,
+};
+
+/// Options of the REGEX to NFA conversion
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Maximum number of links of the produced NFA
+    pub size_limit: Option<usize>,
+    /// Allow look-around assertions checked at the end of a match, such as a
+    /// trailing `$` or `\b`
+    ///
+    /// The NFA accepts whole inputs: an assertion at the end of an accepted
+    /// word sees the end of the input. Prefix executors like [execute_dfa]
+    /// report such a word even when the haystack goes on, and the assertion
+    /// doesn't hold against the next byte, so these assertions are rejected
+    /// unless this is set. Set it when the automaton only runs on whole
+    /// inputs, [Searcher] checks these assertions against the haystack either
+    /// way.
+    pub whole_input: bool,
+}
 
 /// Convert REGEX to NFA
+///
+/// Look-around assertions are evaluated against the bounds of the whole input:
+/// `^` and `\A` only match at its start, `$` and `\z` only at its end. The ones
+/// checked at the end of a match are rejected, see [Config::whole_input].
+/// Unicode word boundaries are not supported, use `(?-u:\b)` instead.
+///
+/// Panics on unsupported syntax, see [try_hir_to_nfa] for a fallible version.
 pub fn hir_to_nfa(hir: &Hir) -> Nfa<u8> {
-    let mut nfa = Automata::new();
-    let mut counter = Counter::new(0);
-    let pair = rec_hir_to_nfa(hir, &mut counter, &mut nfa);
+    try_hir_to_nfa(hir, &Config::default()).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Convert REGEX to NFA, failing on unsupported syntax or exceeded limits
+pub fn try_hir_to_nfa(hir: &Hir, config: &Config) -> Result<Nfa<u8>, BuildError> {
+    compile(hir, config)
+}
+
+/// Convert REGEX to NFA over byte ranges
+///
+/// Ranges of the NFA are split so that any two of them are either equal or
+/// disjoint, the NFA can be determinized right away.
+///
+/// Panics on unsupported syntax, see [try_hir_to_range_nfa] for a fallible version.
+pub fn hir_to_range_nfa(hir: &Hir) -> Nfa<ByteRange> {
+    try_hir_to_range_nfa(hir, &Config::default()).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Convert REGEX to NFA over byte ranges, failing on unsupported syntax or exceeded limits
+pub fn try_hir_to_range_nfa(hir: &Hir, config: &Config) -> Result<Nfa<ByteRange>, BuildError> {
+    let mut nfa = compile(hir, config)?;
+    nfa.split_ranges();
+    check_size_limit(&nfa, config)?;
+    Ok(nfa)
+}
+
+fn compile<S>(hir: &Hir, config: &Config) -> Result<Nfa<S>, BuildError>
+where
+    S: ByteSymbol + Clone,
+{
+    let (nfa, looks) = compile_with_looks(hir, config)?;
+
+    if looks.is_empty() {
+        Ok(nfa)
+    } else {
+        resolve_looks(&nfa, &looks, config)
+    }
+}
+
+/// Convert REGEX to NFA over byte ranges matching anywhere in a haystack, see
+/// [resolve_search_looks]
+pub(crate) fn compile_search(
+    hir: &Hir,
+    config: &Config,
+) -> Result<(Nfa<ByteRange>, Margins), BuildError> {
+    let (nfa, looks) = compile_with_looks(hir, config)?;
+    let (mut nfa, margins) = resolve_search_looks(&nfa, &looks, config)?;
+    nfa.split_ranges();
+    check_size_limit(&nfa, config)?;
+    Ok((nfa, margins))
+}
+
+/// Check the size limit again once ranges are split, splitting adds links
+fn check_size_limit<T>(nfa: &Automata<T>, config: &Config) -> Result<(), BuildError> {
+    match config.size_limit {
+        Some(limit) if nfa.link_count() > limit => Err(BuildError::SizeLimitExceeded { limit }),
+        _ => Ok(()),
+    }
+}
+
+/// Convert REGEX to NFA, leaving look-around assertions unresolved
+fn compile_with_looks<S>(
+    hir: &Hir,
+    config: &Config,
+) -> Result<(Nfa<S>, Vec<Link<Look>>), BuildError>
+where
+    S: ByteSymbol + Clone,
+{
+    let mut compiler = Compiler {
+        config,
+        counter: Counter::new(0),
+        nfa: Automata::new(),
+        looks: Vec::new(),
+    };
+    let pair = rec_hir_to_nfa(hir, &mut compiler)?;
+
+    let Compiler { mut nfa, looks, .. } = compiler;
     nfa.initial_states.insert(pair.start);
     nfa.accept_states.insert(pair.end);
-    nfa
+
+    Ok((nfa, looks))
+}
+
+/// Parse `pattern` and convert it to NFA
+///
+/// Unlike [try_hir_to_nfa], errors on unsupported syntax carry the span of the
+/// offending construct in `pattern`.
+pub fn try_regex_to_nfa(pattern: &str, config: &Config) -> Result<Nfa<u8>, BuildError> {
+    let ast = ast::parse::Parser::new()
+        .parse(pattern)
+        .map_err(|err| BuildError::Syntax(Box::new(err.into())))?;
+    let hir = regex_syntax::hir::translate::Translator::new()
+        .translate(pattern, &ast)
+        .map_err(|err| BuildError::Syntax(Box::new(err.into())))?;
+
+    try_hir_to_nfa(&hir, config).map_err(|err| match err {
+        BuildError::Unsupported {
+            syntax: syntax @ UnsupportedSyntax::UnicodeWordBoundary(_),
+            span: None,
+        } => BuildError::Unsupported {
+            syntax,
+            span: unsupported_span(&ast, &mut true),
+        },
+        err => err,
+    })
+}
+
+/// Find the first Unicode word boundary of the AST, tracking the `u` flag
+fn unsupported_span(ast: &Ast, unicode: &mut bool) -> Option<Span> {
+    match ast {
+        Ast::Flags(set_flags) => {
+            apply_unicode_flag(&set_flags.flags, unicode);
+            None
+        }
+        Ast::Assertion(assertion) => {
+            let is_word_boundary = !matches!(
+                assertion.kind,
+                AssertionKind::StartLine
+                    | AssertionKind::EndLine
+                    | AssertionKind::StartText
+                    | AssertionKind::EndText
+            );
+            (*unicode && is_word_boundary).then_some(assertion.span)
+        }
+        Ast::Repetition(repetition) => unsupported_span(&repetition.ast, unicode),
+        Ast::Group(group) => {
+            let mut unicode = *unicode;
+            if let ast::GroupKind::NonCapturing(flags) = &group.kind {
+                apply_unicode_flag(flags, &mut unicode);
+            }
+            unsupported_span(&group.ast, &mut unicode)
+        }
+        Ast::Alternation(alternation) => alternation
+            .asts
+            .iter()
+            .find_map(|ast| unsupported_span(ast, unicode)),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .find_map(|ast| unsupported_span(ast, unicode)),
+        _ => None,
+    }
+}
+
+fn apply_unicode_flag(flags: &ast::Flags, unicode: &mut bool) {
+    if let Some(enabled) = flags.flag_state(ast::Flag::Unicode) {
+        *unicode = enabled;
+    }
+}
+
+struct Compiler<'a, S> {
+    config: &'a Config,
+    counter: Counter,
+    nfa: Nfa<S>,
+    looks: Vec<Link<Look>>,
+}
+
+impl<S: ByteSymbol> Compiler<'_, S> {
+    fn state(&self) -> Result<State, BuildError> {
+        self.counter.try_next().ok_or(BuildError::StateOverflow)
+    }
+
+    fn link(&mut self, from: State, to: State, symbol: MaybeSymbol<S>) -> Result<(), BuildError> {
+        if let Some(limit) = self.config.size_limit
+            && self.nfa.link_count() >= limit
+        {
+            return Err(BuildError::SizeLimitExceeded { limit });
+        }
+        self.nfa.link(from, to, symbol);
+        Ok(())
+    }
+
+    /// Link `from` to `to` with symbols covering every byte in `start..=end`
+    fn link_range(&mut self, from: State, to: State, start: u8, end: u8) -> Result<(), BuildError> {
+        for symbol in S::from_range(start, end) {
+            self.link(from, to, MaybeSymbol::Symbol(symbol))?;
+        }
+        Ok(())
+    }
 }
 
 struct Pair {
@@ -17,123 +223,129 @@ struct Pair {
     end: State,
 }
 
-fn rec_hir_to_nfa(hir: &Hir, counter: &Counter, nfa: &mut Nfa<u8>) -> Pair {
+fn rec_hir_to_nfa<S: ByteSymbol>(
+    hir: &Hir,
+    compiler: &mut Compiler<S>,
+) -> Result<Pair, BuildError> {
     use crate::MaybeSymbol::*;
 
-    let start = counter.next();
-    let end = counter.next();
+    let start = compiler.state()?;
+    let end = compiler.state()?;
     match hir.kind() {
-        HirKind::Look(_) => panic!("Look around are not supported"),
+        HirKind::Look(look) => {
+            if is_unicode_word_look(*look) {
+                return Err(BuildError::Unsupported {
+                    syntax: UnsupportedSyntax::UnicodeWordBoundary(*look),
+                    span: None,
+                });
+            }
+            // conditional epsilon link, resolved once the whole NFA is built
+            compiler.looks.push(Link {
+                from: start,
+                to: end,
+                symbol: *look,
+            });
+        }
         HirKind::Empty => {
-            nfa.link(start, end, Epsilon);
+            compiler.link(start, end, Epsilon)?;
         }
         HirKind::Literal(Literal(bytes)) => {
             let mut prev = start;
             for byte in bytes.iter() {
-                let next = counter.next();
-                nfa.link(prev, next, Symbol(*byte));
+                let next = compiler.state()?;
+                compiler.link_range(prev, next, *byte, *byte)?;
                 prev = next;
             }
-            nfa.link(prev, end, Epsilon);
+            compiler.link(prev, end, Epsilon)?;
         }
         HirKind::Class(class) => match class {
             Class::Unicode(class) => {
-                // Compile unicode code point ranges into byte transitions (prefix-tree)
-                let code_point = class.ranges().iter().flat_map(|range| {
-                    (range.start()..=range.end()).map(|c| {
-                        let mut bytes = [0; 4];
-                        c.encode_utf8(&mut bytes);
-                        (c.len_utf8(), bytes)
-                    })
-                });
-
-                let mut prev_len = 0;
-                let mut prev_bytes = [0; 4];
-                let mut common_states: [State; 4] = [0; 4];
-                let mut common_len = 0;
-
-                for (len, bytes) in code_point {
-                    assert!(prev_len <= 4);
-                    assert!(1 <= len && len <= 4);
-
-                    let mut prev_state = start;
-                    for i in 0..len {
-                        let prev_byte = prev_bytes[i];
-                        let byte = bytes[i];
+                // Compile code point ranges into UTF-8 byte range sequences,
+                // sharing the common suffixes of the sequences
+                let mut suffixes: FxHashMap<(u8, u8, State), State> = Default::default();
 
-                        if i >= common_len || prev_byte != byte {
-                            common_len = i;
-
-                            let next_state = counter.next();
-                            common_states[i] = next_state;
+                for range in class.ranges() {
+                    for sequence in Utf8Sequences::new(range.start(), range.end()) {
+                        let (first, rest) = sequence.as_slice().split_first().unwrap();
 
-                            nfa.link(prev_state, next_state, Symbol(byte));
-                            prev_state = next_state;
-                        } else {
-                            prev_state = common_states[i];
+                        let mut next = end;
+                        for range in rest.iter().rev() {
+                            next = match suffixes.get(&(range.start, range.end, next)) {
+                                Some(state) => *state,
+                                None => {
+                                    let state = compiler.state()?;
+                                    compiler.link_range(state, next, range.start, range.end)?;
+                                    suffixes.insert((range.start, range.end, next), state);
+                                    state
+                                }
+                            };
                         }
-                    }
 
-                    nfa.link(prev_state, end, Epsilon);
-                    prev_len = len;
-                    common_len = len;
-                    prev_bytes = bytes;
+                        compiler.link_range(start, next, first.start, first.end)?;
+                    }
                 }
             }
             Class::Bytes(class) => {
                 for range in class.ranges() {
-                    for byte in range.start()..=range.end() {
-                        nfa.link(start, end, Symbol(byte));
-                    }
+                    compiler.link_range(start, end, range.start(), range.end())?;
                 }
             }
         },
         HirKind::Repetition(repetition) => {
             let mut prev = start;
             for _ in 0..repetition.min {
-                let item = rec_hir_to_nfa(&repetition.sub, counter, nfa);
-                nfa.link(prev, item.start, Epsilon);
+                let item = rec_hir_to_nfa(&repetition.sub, compiler)?;
+                compiler.link(prev, item.start, Epsilon)?;
                 prev = item.end;
             }
 
+            // Repeat once more or stop, links are added by order of priority
+            let choice = |compiler: &mut Compiler<S>, from: State, again: State| {
+                if repetition.greedy {
+                    compiler.link(from, again, Epsilon)?;
+                    compiler.link(from, end, Epsilon)
+                } else {
+                    compiler.link(from, end, Epsilon)?;
+                    compiler.link(from, again, Epsilon)
+                }
+            };
+
             if let Some(max) = repetition.max {
                 for _ in repetition.min..max {
-                    let item = rec_hir_to_nfa(&repetition.sub, counter, nfa);
-                    nfa.link(prev, item.start, Epsilon);
-                    nfa.link(prev, end, Epsilon);
+                    let item = rec_hir_to_nfa(&repetition.sub, compiler)?;
+                    choice(compiler, prev, item.start)?;
                     prev = item.end;
                 }
+                compiler.link(prev, end, Epsilon)?;
             } else {
-                let item = rec_hir_to_nfa(&repetition.sub, counter, nfa);
-                nfa.link(prev, item.start, Epsilon);
-                nfa.link(item.end, item.start, Epsilon);
-                nfa.link(item.end, end, Epsilon);
+                let item = rec_hir_to_nfa(&repetition.sub, compiler)?;
+                choice(compiler, prev, item.start)?;
+                choice(compiler, item.end, item.start)?;
             }
-            nfa.link(prev, end, Epsilon);
         }
         HirKind::Capture(capture) => {
             // ignore captures
-            let item = rec_hir_to_nfa(&capture.sub, counter, nfa);
-            nfa.link(start, item.start, Epsilon);
-            nfa.link(item.end, end, Epsilon);
+            let item = rec_hir_to_nfa(&capture.sub, compiler)?;
+            compiler.link(start, item.start, Epsilon)?;
+            compiler.link(item.end, end, Epsilon)?;
         }
         HirKind::Concat(list) => {
             let mut prev = start;
             for item in list {
-                let item = rec_hir_to_nfa(item, counter, nfa);
-                nfa.link(prev, item.start, Epsilon);
+                let item = rec_hir_to_nfa(item, compiler)?;
+                compiler.link(prev, item.start, Epsilon)?;
                 prev = item.end;
             }
-            nfa.link(prev, end, Epsilon);
+            compiler.link(prev, end, Epsilon)?;
         }
         HirKind::Alternation(list) => {
             for item in list {
-                let item = rec_hir_to_nfa(item, counter, nfa);
-                nfa.link(start, item.start, Epsilon);
-                nfa.link(item.end, end, Epsilon);
+                let item = rec_hir_to_nfa(item, compiler)?;
+                compiler.link(start, item.start, Epsilon)?;
+                compiler.link(item.end, end, Epsilon)?;
             }
         }
     }
 
-    Pair { start, end }
+    Ok(Pair { start, end })
 }
@@ -59,6 +59,6 @@ where
             }
         }
 
-        return vec;
+        vec
     }
 }
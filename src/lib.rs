@@ -1,16 +1,41 @@
+mod analysis;
 mod automata;
+mod byte_range;
+mod combine;
+mod compare;
+mod complement;
 mod counter;
+mod dense;
 mod determine;
 mod dfa_to_hir;
 mod dot;
+mod error;
 mod execute;
 mod hir_to_nfa;
 mod iter_ext;
+mod look;
+mod minimize;
+mod product;
+mod quotient;
+mod sample;
+mod search;
+mod step;
+mod stream;
+mod trim;
+mod words;
 
 pub use automata::*;
+pub use byte_range::*;
+pub use compare::*;
 pub use counter::*;
+pub use dense::*;
 pub use determine::*;
 pub use dfa_to_hir::*;
+pub use error::*;
 pub use execute::*;
 pub use hir_to_nfa::*;
 pub use iter_ext::*;
+pub use minimize::*;
+pub use search::*;
+pub use step::*;
+pub use stream::*;
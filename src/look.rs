@@ -0,0 +1,342 @@
+use regex_syntax::{hir::Look, is_word_byte};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::*;
+
+/// Kind of the symbol surrounding a position, as seen by look-around assertions.
+///
+/// `Boundary` stands for the start of the input when looking behind and for
+/// the end of the input when looking ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Context {
+    Boundary,
+    LineFeed,
+    CarriageReturn,
+    Word,
+    Other,
+}
+
+impl Context {
+    const ALL: [Context; 5] = [
+        Context::Boundary,
+        Context::LineFeed,
+        Context::CarriageReturn,
+        Context::Word,
+        Context::Other,
+    ];
+
+    fn of(byte: u8) -> Self {
+        match byte {
+            b'\n' => Context::LineFeed,
+            b'\r' => Context::CarriageReturn,
+            _ if is_word_byte(byte) => Context::Word,
+            _ => Context::Other,
+        }
+    }
+
+    /// Split `start..=end` into sub ranges of bytes sharing the same context
+    fn split(start: u8, end: u8) -> impl Iterator<Item = (Context, u8, u8)> {
+        let mut next = Some(start);
+        std::iter::from_fn(move || {
+            let start = next?;
+            let context = Context::of(start);
+            let mut end_of_split = start;
+            while end_of_split < end && Context::of(end_of_split + 1) == context {
+                end_of_split += 1;
+            }
+            next = if end_of_split < end {
+                Some(end_of_split + 1)
+            } else {
+                None
+            };
+            Some((context, start, end_of_split))
+        })
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn is_word(self) -> bool {
+        self == Context::Word
+    }
+}
+
+/// Set of contexts the next symbol is still allowed to have
+const ANY_NEXT: u8 = (1 << Context::ALL.len()) - 1;
+
+/// Returns `true` for the Unicode flavours of word boundary assertions
+pub(crate) fn is_unicode_word_look(look: Look) -> bool {
+    matches!(
+        look,
+        Look::WordUnicode
+            | Look::WordUnicodeNegate
+            | Look::WordStartUnicode
+            | Look::WordEndUnicode
+            | Look::WordStartHalfUnicode
+            | Look::WordEndHalfUnicode
+    )
+}
+
+/// Check if `look` holds between a `prev` and a `next` symbol
+fn look_holds(look: Look, prev: Context, next: Context) -> bool {
+    use Context::*;
+
+    match look {
+        Look::Start => prev == Boundary,
+        Look::End => next == Boundary,
+        Look::StartLF => matches!(prev, Boundary | LineFeed),
+        Look::EndLF => matches!(next, Boundary | LineFeed),
+        Look::StartCRLF => {
+            matches!(prev, Boundary | LineFeed) || (prev == CarriageReturn && next != LineFeed)
+        }
+        Look::EndCRLF => {
+            matches!(next, Boundary | CarriageReturn)
+                || (next == LineFeed && prev != CarriageReturn)
+        }
+        Look::WordAscii => prev.is_word() != next.is_word(),
+        Look::WordAsciiNegate => prev.is_word() == next.is_word(),
+        Look::WordStartAscii => !prev.is_word() && next.is_word(),
+        Look::WordEndAscii => prev.is_word() && !next.is_word(),
+        Look::WordStartHalfAscii => !prev.is_word(),
+        Look::WordEndHalfAscii => !next.is_word(),
+        _ => unreachable!("Unicode word boundaries are rejected before resolution"),
+    }
+}
+
+/// Resolve conditional epsilon links (look-around assertions) into a plain NFA
+///
+/// Each state of the resulting NFA is a state of `nfa` paired with the context
+/// of the previous symbol (lookbehind) and the set of contexts the next symbol
+/// may have (pending lookahead). Lookahead constraints are checked when the
+/// next symbol is consumed, or against the end of the input on accept states.
+pub(crate) fn resolve_looks<S>(
+    nfa: &Nfa<S>,
+    looks: &[Link<Look>],
+    config: &Config,
+) -> Result<Nfa<S>, BuildError>
+where
+    S: ByteSymbol + Clone,
+{
+    let mut resolver = Resolver::new(nfa, looks, config);
+
+    for initial_state in &nfa.initial_states {
+        let state = resolver.state((*initial_state, Context::Boundary, ANY_NEXT))?;
+        resolver.resolved.initial_states.insert(state);
+    }
+
+    // Accept states whose acceptance depends on the symbol after the match
+    let mut pending_look = false;
+    resolver.run(|resolver, from, next| {
+        pending_look |= next != ANY_NEXT;
+        if next & Context::Boundary.bit() != 0 {
+            resolver.resolved.accept_states.insert(from);
+        }
+        Ok(())
+    })?;
+
+    if !config.whole_input && pending_look {
+        return Err(BuildError::Unsupported {
+            syntax: UnsupportedSyntax::LookAtMatchEnd,
+            span: None,
+        });
+    }
+
+    Ok(resolver.resolved)
+}
+
+/// Extra states of an NFA resolved for searching, see [resolve_search_looks]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Margins {
+    /// Start of a match at the start of the haystack
+    pub start: State,
+    /// Start of a match, one byte before it: that byte is consumed first
+    pub inner_start: State,
+    /// End of a match, one byte after it: reached by consuming that byte
+    pub inner_end: State,
+    /// End of a match at the end of the haystack
+    pub end: State,
+}
+
+/// Resolve look-around assertions of an NFA matching anywhere in a haystack
+///
+/// Unlike [resolve_looks], the bytes surrounding a match are part of its
+/// context: the resolved NFA has no initial nor accept states, matches go from
+/// one of the start [Margins] to one of the end ones.
+pub(crate) fn resolve_search_looks<S>(
+    nfa: &Nfa<S>,
+    looks: &[Link<Look>],
+    config: &Config,
+) -> Result<(Nfa<S>, Margins), BuildError>
+where
+    S: ByteSymbol + Clone,
+{
+    use crate::MaybeSymbol::*;
+
+    let mut resolver = Resolver::new(nfa, looks, config);
+    let margins = Margins {
+        start: resolver
+            .counter
+            .try_next()
+            .ok_or(BuildError::StateOverflow)?,
+        inner_start: resolver
+            .counter
+            .try_next()
+            .ok_or(BuildError::StateOverflow)?,
+        inner_end: resolver
+            .counter
+            .try_next()
+            .ok_or(BuildError::StateOverflow)?,
+        end: resolver
+            .counter
+            .try_next()
+            .ok_or(BuildError::StateOverflow)?,
+    };
+
+    for initial_state in &nfa.initial_states {
+        let state = resolver.state((*initial_state, Context::Boundary, ANY_NEXT))?;
+        resolver.link(margins.start, state, Epsilon)?;
+
+        for (context, start, end) in Context::split(u8::MIN, u8::MAX) {
+            let state = resolver.state((*initial_state, context, ANY_NEXT))?;
+            for symbol in S::from_range(start, end) {
+                resolver.link(margins.inner_start, state, Symbol(symbol))?;
+            }
+        }
+    }
+
+    resolver.run(|resolver, from, next| {
+        if next & Context::Boundary.bit() != 0 {
+            resolver.link(from, margins.end, Epsilon)?;
+        }
+        for (context, start, end) in Context::split(u8::MIN, u8::MAX) {
+            if next & context.bit() != 0 {
+                for symbol in S::from_range(start, end) {
+                    resolver.link(from, margins.inner_end, Symbol(symbol))?;
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok((resolver.resolved, margins))
+}
+
+/// Product construction shared by the resolutions
+struct Resolver<'a, S> {
+    config: &'a Config,
+    links_from: FxHashMap<State, Vec<&'a NfaLink<S>>>,
+    looks_from: FxHashMap<State, Vec<&'a Link<Look>>>,
+    accept_states: &'a FxHashSet<State>,
+    resolved: Nfa<S>,
+    counter: Counter,
+    mapping: FxHashMap<(State, Context, u8), State>,
+    to_explore: Vec<(State, Context, u8)>,
+}
+
+impl<'a, S> Resolver<'a, S>
+where
+    S: ByteSymbol + Clone,
+{
+    fn new(nfa: &'a Nfa<S>, looks: &'a [Link<Look>], config: &'a Config) -> Self {
+        let mut links_from: FxHashMap<State, Vec<&NfaLink<S>>> = Default::default();
+        for link in nfa.links() {
+            links_from.entry(link.from).or_default().push(link);
+        }
+
+        let mut looks_from: FxHashMap<State, Vec<&Link<Look>>> = Default::default();
+        for look in looks {
+            looks_from.entry(look.from).or_default().push(look);
+        }
+
+        Self {
+            config,
+            links_from,
+            looks_from,
+            accept_states: &nfa.accept_states,
+            resolved: Nfa::new(),
+            counter: Counter::new(0),
+            mapping: Default::default(),
+            to_explore: Vec::new(),
+        }
+    }
+
+    /// Resolved state of `key`, queued for exploration when new
+    fn state(&mut self, key: (State, Context, u8)) -> Result<State, BuildError> {
+        if let Some(state) = self.mapping.get(&key) {
+            return Ok(*state);
+        }
+        let state = self.counter.try_next().ok_or(BuildError::StateOverflow)?;
+        self.mapping.insert(key, state);
+        self.to_explore.push(key);
+        Ok(state)
+    }
+
+    /// Add a link to the resolved NFA, within the configured size limit
+    fn link(&mut self, from: State, to: State, symbol: MaybeSymbol<S>) -> Result<(), BuildError> {
+        if let Some(limit) = self.config.size_limit
+            && self.resolved.link_count() >= limit
+        {
+            return Err(BuildError::SizeLimitExceeded { limit });
+        }
+        self.resolved.link(from, to, symbol);
+        Ok(())
+    }
+
+    /// Explore the product from the queued states, `accept` is called on the
+    /// resolved accept states along with the contexts still allowed next
+    fn run(
+        &mut self,
+        mut accept: impl FnMut(&mut Self, State, u8) -> Result<(), BuildError>,
+    ) -> Result<(), BuildError> {
+        use crate::MaybeSymbol::*;
+
+        while let Some(key @ (state, prev, next)) = self.to_explore.pop() {
+            let from = self.state(key)?;
+
+            if self.accept_states.contains(&state) {
+                accept(self, from, next)?;
+            }
+
+            let links = self.links_from.get(&state).cloned().unwrap_or_default();
+            for link in links {
+                match &link.symbol {
+                    Epsilon => {
+                        let to = self.state((link.to, prev, next))?;
+                        self.link(from, to, Epsilon)?;
+                    }
+                    Symbol(symbol) => {
+                        let (start, end) = symbol.bounds();
+                        for (context, start, end) in Context::split(start, end) {
+                            if next & context.bit() == 0 {
+                                continue;
+                            }
+                            let to = self.state((link.to, context, ANY_NEXT))?;
+                            for symbol in S::from_range(start, end) {
+                                self.link(from, to, Symbol(symbol))?;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let looks = self.looks_from.get(&state).cloned().unwrap_or_default();
+            for look in looks {
+                let allowed = Context::ALL
+                    .into_iter()
+                    .filter(|ctx| look_holds(look.symbol, prev, *ctx))
+                    .fold(0, |acc, ctx| acc | ctx.bit());
+
+                let next = next & allowed;
+                if next == 0 {
+                    continue;
+                }
+
+                let to = self.state((look.to, prev, next))?;
+                self.link(from, to, Epsilon)?;
+            }
+        }
+
+        Ok(())
+    }
+}
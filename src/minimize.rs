@@ -0,0 +1,285 @@
+use std::{collections::hash_map::Entry, hash::Hash};
+
+use rustc_hash::FxHashMap;
+
+use crate::*;
+
+/// Minimize DFA using Hopcroft's partition refinement algorithm
+///
+/// States that are not both reachable and coreachable are removed first. The
+/// remaining partial DFA is refined as is, without completing it with a dead
+/// state (Valmari and Lehtinen's variant): blocks of states are split by the
+/// links reaching them, grouped by symbol. Runs in O(n + m·log m) for `n`
+/// states and `m` links.
+pub fn minimize_hopcroft<T>(dfa: Dfa<T>) -> Dfa<T>
+where
+    T: Eq + Hash + Clone,
+{
+    let initial_state = dfa.initial_state();
+
+    // Dense indices of the reachable states, in discovery order
+    let mut indices: FxHashMap<State, usize> = Default::default();
+    let mut states = vec![initial_state];
+    indices.insert(initial_state, 0);
+    let mut links_from: Vec<Vec<&DfaLink<T>>> = vec![Vec::new()];
+    {
+        let mut all_links_from: FxHashMap<State, Vec<&DfaLink<T>>> = Default::default();
+        for link in dfa.links() {
+            all_links_from.entry(link.from).or_default().push(link);
+        }
+
+        let mut i = 0;
+        while i < states.len() {
+            let links = all_links_from.remove(&states[i]).unwrap_or_default();
+            for link in &links {
+                if let Entry::Vacant(entry) = indices.entry(link.to) {
+                    entry.insert(states.len());
+                    states.push(link.to);
+                    links_from.push(Vec::new());
+                }
+            }
+            links_from[i] = links;
+            i += 1;
+        }
+    }
+    let len = states.len();
+
+    // Keep the states an accept state is reachable from
+    let mut links_to: Vec<Vec<usize>> = vec![Vec::new(); len];
+    for (from, links) in links_from.iter().enumerate() {
+        for link in links {
+            links_to[indices[&link.to]].push(from);
+        }
+    }
+    let mut useful = vec![false; len];
+    let mut to_explore: Vec<usize> = (0..len)
+        .filter(|index| dfa.accept_states.contains(&states[*index]))
+        .collect();
+    while let Some(index) = to_explore.pop() {
+        if !std::mem::replace(&mut useful[index], true) {
+            to_explore.extend(links_to[index].iter().copied());
+        }
+    }
+    if !useful[0] {
+        // No accepting state, the language is empty
+        let mut empty = Dfa::new();
+        empty.initial_states.insert(0);
+        return empty;
+    }
+
+    // Links between useful states, as `(from, symbol, to)` sorted by symbol
+    let mut symbols: FxHashMap<&T, usize> = Default::default();
+    let mut transitions: Vec<(usize, usize, usize)> = Vec::new();
+    for (from, links) in links_from.iter().enumerate() {
+        for link in links {
+            let to = indices[&link.to];
+            if useful[from] && useful[to] {
+                let next = symbols.len();
+                let symbol = *symbols.entry(&link.symbol).or_insert(next);
+                transitions.push((from, symbol, to));
+            }
+        }
+    }
+    transitions.sort_by_key(|(_, symbol, _)| *symbol);
+
+    // Links reaching each state: `incoming[incoming_start[s]..incoming_start[s + 1]]`
+    let mut incoming_start = vec![0; len + 1];
+    for (_, _, to) in &transitions {
+        incoming_start[to + 1] += 1;
+    }
+    for state in 0..len {
+        incoming_start[state + 1] += incoming_start[state];
+    }
+    let mut incoming = vec![0; transitions.len()];
+    let mut next_incoming = incoming_start.clone();
+    for (transition, (_, _, to)) in transitions.iter().enumerate() {
+        incoming[next_incoming[*to]] = transition;
+        next_incoming[*to] += 1;
+    }
+
+    // Blocks of states, the first one holds all the useful states
+    let mut blocks = Partition::new((0..len).filter(|index| useful[*index]).collect(), len);
+    for index in 0..len {
+        if useful[index] && dfa.accept_states.contains(&states[index]) {
+            blocks.mark(index);
+        }
+    }
+    blocks.split();
+
+    // Cords: links with the same symbol
+    let mut cords = Partition::new((0..transitions.len()).collect(), transitions.len());
+    cords.sets.clear();
+    for (transition, (_, symbol, _)) in transitions.iter().enumerate() {
+        if transition == 0 || transitions[transition - 1].1 != *symbol {
+            cords.sets.push(Set::new(transition));
+        }
+        let set = cords.sets.len() - 1;
+        cords.sets[set].end = transition + 1;
+        cords.set_of[transition] = set;
+    }
+
+    // Each cord splits blocks by the states its links leave from, each block
+    // but the first one splits cords by the links reaching it. New sets are
+    // the smaller parts of the split ones.
+    let mut cord = 0;
+    let mut block = 1;
+    while cord < cords.sets.len() {
+        for position in cords.sets[cord].start..cords.sets[cord].end {
+            let (from, _, _) = transitions[cords.elements[position]];
+            blocks.mark(from);
+        }
+        blocks.split();
+        cord += 1;
+
+        while block < blocks.sets.len() {
+            for position in blocks.sets[block].start..blocks.sets[block].end {
+                let state = blocks.elements[position];
+                for transition in &incoming[incoming_start[state]..incoming_start[state + 1]] {
+                    cords.mark(*transition);
+                }
+            }
+            cords.split();
+            block += 1;
+        }
+    }
+
+    // Build the minimal DFA from blocks, numbered in discovery order
+    let counter = Counter::new(0);
+    let mut block_states: FxHashMap<usize, State> = Default::default();
+    let mut minimal = Dfa::new();
+
+    let initial_block = blocks.set_of[0];
+    block_states.insert(initial_block, counter.next());
+    minimal.initial_states.insert(block_states[&initial_block]);
+
+    let mut to_explore = vec![initial_block];
+    while let Some(block) = to_explore.pop() {
+        let from = block_states[&block];
+        let representative = blocks.elements[blocks.sets[block].start];
+
+        if dfa.accept_states.contains(&states[representative]) {
+            minimal.accept_states.insert(from);
+        }
+
+        for link in &links_from[representative] {
+            let to = indices[&link.to];
+            if !useful[to] {
+                continue;
+            }
+            let to_block = blocks.set_of[to];
+            let to = *block_states.entry(to_block).or_insert_with(|| {
+                to_explore.push(to_block);
+                counter.next()
+            });
+            minimal.link(from, to, link.symbol.clone());
+        }
+    }
+
+    minimal
+}
+
+/// Refinable partition of `0..len`: the elements of each set are contiguous
+/// in `elements`, marked ones first
+struct Partition {
+    elements: Vec<usize>,
+    /// Position of each element in `elements`
+    positions: Vec<usize>,
+    set_of: Vec<usize>,
+    sets: Vec<Set>,
+    /// Sets with marked elements
+    touched: Vec<usize>,
+}
+
+struct Set {
+    start: usize,
+    end: usize,
+    marked: usize,
+}
+
+impl Set {
+    fn new(start: usize) -> Self {
+        Set {
+            start,
+            end: start,
+            marked: 0,
+        }
+    }
+}
+
+impl Partition {
+    /// Partition with a single set made of `elements`, each lower than `len`
+    fn new(elements: Vec<usize>, len: usize) -> Self {
+        let mut positions = vec![0; len];
+        for (position, element) in elements.iter().enumerate() {
+            positions[*element] = position;
+        }
+        let set = Set {
+            start: 0,
+            end: elements.len(),
+            marked: 0,
+        };
+        Partition {
+            elements,
+            positions,
+            set_of: vec![0; len],
+            sets: vec![set],
+            touched: Vec::new(),
+        }
+    }
+
+    /// Mark `element` by swapping it with the first unmarked one of its set
+    fn mark(&mut self, element: usize) {
+        let set = &mut self.sets[self.set_of[element]];
+        let position = self.positions[element];
+        let first_unmarked = set.start + set.marked;
+        if position < first_unmarked {
+            return;
+        }
+
+        let other = self.elements[first_unmarked];
+        self.elements.swap(position, first_unmarked);
+        self.positions[other] = position;
+        self.positions[element] = first_unmarked;
+
+        if set.marked == 0 {
+            self.touched.push(self.set_of[element]);
+        }
+        set.marked += 1;
+    }
+
+    /// Split the touched sets into their marked and unmarked elements, the
+    /// smaller part becomes a new set
+    fn split(&mut self) {
+        while let Some(index) = self.touched.pop() {
+            let set = &mut self.sets[index];
+            let middle = set.start + set.marked;
+            set.marked = 0;
+            if middle == set.end {
+                continue;
+            }
+
+            let new_set = if middle - set.start <= set.end - middle {
+                let new_set = Set {
+                    start: set.start,
+                    end: middle,
+                    marked: 0,
+                };
+                set.start = middle;
+                new_set
+            } else {
+                let new_set = Set {
+                    start: middle,
+                    end: set.end,
+                    marked: 0,
+                };
+                set.end = middle;
+                new_set
+            };
+
+            for position in new_set.start..new_set.end {
+                self.set_of[self.elements[position]] = self.sets.len();
+            }
+            self.sets.push(new_set);
+        }
+    }
+}
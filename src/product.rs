@@ -0,0 +1,138 @@
+use std::{fmt::Debug, hash::Hash};
+
+use rustc_hash::FxHashMap;
+
+use crate::{byte_range::aligned_ranges, *};
+
+impl<T> Dfa<T>
+where
+    T: Eq + Hash + Clone + 'static,
+{
+    /// DFA accepting the words accepted by both `self` and `other`
+    ///
+    /// Product construction, only the product states reachable from the pair
+    /// of initial states are created. Symbols are matched by equality.
+    pub fn intersect(&self, other: &Self) -> Dfa<T> {
+        self.product(other, |left, right| left && right)
+    }
+
+    /// Minimal DFA accepting the words accepted by `self` but not by `other`
+    pub fn difference(&self, other: &Self) -> Dfa<T> {
+        minimize_hopcroft(self.product(other, |left, right| left && !right))
+    }
+
+    /// Minimal DFA accepting the words accepted by exactly one of `self` and `other`
+    pub fn symmetric_difference(&self, other: &Self) -> Dfa<T> {
+        minimize_hopcroft(self.product(other, |left, right| left != right))
+    }
+
+    /// Product of `self` and `other`, a pair is accepting when `accept` holds
+    /// for whether each side accepts
+    ///
+    /// A side with no link for a symbol goes to an implicit dead state, `None`,
+    /// only when `accept` can hold while that side is dead.
+    fn product(&self, other: &Self, accept: impl Fn(bool, bool) -> bool) -> Dfa<T> {
+        if let Some((left, right)) = aligned_ranges(self, other) {
+            return left.product_aligned(&right, accept);
+        }
+        self.product_aligned(other, accept)
+    }
+
+    fn product_aligned(&self, other: &Self, accept: impl Fn(bool, bool) -> bool) -> Dfa<T> {
+        let complete_left = accept(false, true) || accept(false, false);
+        let complete_right = accept(true, false) || accept(false, false);
+
+        let mut product = Dfa::new();
+        let counter = Counter::new(0);
+        let mut pairs: FxHashMap<(Option<State>, Option<State>), State> = Default::default();
+        let mut to_explore = Vec::new();
+
+        let initial = (Some(self.initial_state()), Some(other.initial_state()));
+        pairs.insert(initial, counter.next());
+        product.initial_states.insert(pairs[&initial]);
+        to_explore.push(initial);
+
+        while let Some(pair @ (left, right)) = to_explore.pop() {
+            let from = pairs[&pair];
+            let left_accepts = left.is_some_and(|state| self.accept_states.contains(&state));
+            let right_accepts = right.is_some_and(|state| other.accept_states.contains(&state));
+            if accept(left_accepts, right_accepts) {
+                product.accept_states.insert(from);
+            }
+
+            let mut targets: FxHashMap<&T, (Option<State>, Option<State>)> = Default::default();
+            for link in left.into_iter().flat_map(|state| self.links_from(state)) {
+                targets.entry(&link.symbol).or_default().0 = Some(link.to);
+            }
+            for link in right.into_iter().flat_map(|state| other.links_from(state)) {
+                targets.entry(&link.symbol).or_default().1 = Some(link.to);
+            }
+
+            for (symbol, to_pair) in targets {
+                let reachable = match to_pair {
+                    (Some(_), Some(_)) => true,
+                    (Some(_), None) => complete_right,
+                    (None, Some(_)) => complete_left,
+                    (None, None) => false,
+                };
+                if !reachable {
+                    continue;
+                }
+
+                let to = *pairs.entry(to_pair).or_insert_with(|| {
+                    to_explore.push(to_pair);
+                    counter.next()
+                });
+                product.link(from, to, symbol.clone());
+            }
+        }
+
+        product
+    }
+}
+
+impl<T> Dfa<T>
+where
+    T: Eq + Hash + Clone + Debug + 'static,
+{
+    /// Minimal DFA accepting the words accepted by `self` or `other`
+    ///
+    /// Both DFAs are put side by side in an NFA, `other` being renumbered
+    /// after the states of `self`, behind a fresh initial state.
+    pub fn union(&self, other: &Self) -> Dfa<T> {
+        if let Some((left, right)) = aligned_ranges(self, other) {
+            return left.union_aligned(&right);
+        }
+        self.union_aligned(other)
+    }
+
+    fn union_aligned(&self, other: &Self) -> Dfa<T> {
+        let mut nfa = self.clone().into_nfa();
+        let counter = self.next_counter();
+
+        let mut renumbered: FxHashMap<State, State> = Default::default();
+        for state in other.states() {
+            renumbered.entry(state).or_insert_with(|| counter.next());
+        }
+
+        for link in other.links() {
+            nfa.link(
+                renumbered[&link.from],
+                renumbered[&link.to],
+                MaybeSymbol::Symbol(link.symbol.clone()),
+            );
+        }
+        nfa.accept_states
+            .extend(other.accept_states.iter().map(|state| renumbered[state]));
+
+        let initial = counter.next();
+        let initial_states = std::mem::take(&mut nfa.initial_states);
+        let other_initial_states = other.initial_states.iter().map(|state| renumbered[state]);
+        for state in initial_states.into_iter().chain(other_initial_states) {
+            nfa.link(initial, state, MaybeSymbol::Epsilon);
+        }
+        nfa.initial_states.insert(initial);
+
+        determine_and_minimize_nfa_with(nfa, Minimization::Hopcroft)
+    }
+}
@@ -0,0 +1,165 @@
+use std::{fmt::Debug, hash::Hash};
+
+use rustc_hash::FxHashSet;
+
+use crate::*;
+
+impl<T> Dfa<T>
+where
+    T: Eq + Hash + Clone + Debug,
+{
+    /// Minimal DFA accepting every prefix of the accepted words
+    ///
+    /// Every state an accept state is reachable from becomes accepting.
+    pub fn prefixes(&self) -> Dfa<T> {
+        let mut dfa = self.clone();
+        dfa.accept_states = self.coreachable_states();
+        minimize_hopcroft(dfa)
+    }
+
+    /// Minimal DFA accepting every suffix of the accepted words
+    ///
+    /// Every useful state becomes initial, the resulting NFA is determinized.
+    pub fn suffixes(&self) -> Dfa<T> {
+        let useful = self.useful_states();
+        if useful.is_empty() {
+            return self.rejecting();
+        }
+
+        let mut nfa = self.clone().into_nfa();
+        nfa.initial_states = useful;
+        determine_and_minimize_nfa_with(nfa, Minimization::Hopcroft)
+    }
+
+    /// Minimal DFA accepting every factor (substring) of the accepted words
+    ///
+    /// Every useful state becomes both initial and accepting.
+    pub fn factors(&self) -> Dfa<T> {
+        let useful = self.useful_states();
+        if useful.is_empty() {
+            return self.rejecting();
+        }
+
+        let mut nfa = self.clone().into_nfa();
+        nfa.initial_states = useful.clone();
+        nfa.accept_states = useful;
+        determine_and_minimize_nfa_with(nfa, Minimization::Hopcroft)
+    }
+
+    /// Minimal DFA accepting the words `v` such that `word` followed by `v`
+    /// is accepted (left quotient `u⁻¹L`)
+    ///
+    /// The DFA starts from the state reached after `word`. The result is empty
+    /// when no accepted word starts with `word`.
+    pub fn left_quotient(&self, word: &[T]) -> Dfa<T> {
+        let Some(state) = self.run(self.initial_state(), word) else {
+            return self.rejecting();
+        };
+
+        let mut dfa = self.clone();
+        dfa.initial_states = FxHashSet::from_iter([state]);
+        minimize_hopcroft(dfa)
+    }
+
+    /// Minimal DFA accepting the words `v` such that `v` followed by `word`
+    /// is accepted (right quotient `Lu⁻¹`)
+    ///
+    /// The states reaching an accept state after `word` become accepting.
+    pub fn right_quotient(&self, word: &[T]) -> Dfa<T> {
+        let mut dfa = self.clone();
+        dfa.accept_states = self
+            .states_set()
+            .into_iter()
+            .filter(|state| {
+                self.run(*state, word)
+                    .is_some_and(|to| self.accept_states.contains(&to))
+            })
+            .collect();
+        minimize_hopcroft(dfa)
+    }
+
+    /// Minimal DFA accepting the words `v` such that a word of `other`
+    /// followed by `v` is accepted (left quotient `K⁻¹L`)
+    ///
+    /// The states of `self` reachable with a word accepted by `other` become
+    /// initial, found by a search of the product of the DFAs.
+    pub fn left_quotient_by(&self, other: &Self) -> Dfa<T> {
+        let initial = (other.initial_state(), self.initial_state());
+        let mut explored = FxHashSet::from_iter([initial]);
+        let mut to_explore = vec![initial];
+        let mut initial_states = FxHashSet::default();
+
+        while let Some((other_state, state)) = to_explore.pop() {
+            if other.accept_states.contains(&other_state) {
+                initial_states.insert(state);
+            }
+            for other_link in other.links_from(other_state) {
+                for link in self.links_from(state) {
+                    let to = (other_link.to, link.to);
+                    if link.symbol == other_link.symbol && explored.insert(to) {
+                        to_explore.push(to);
+                    }
+                }
+            }
+        }
+
+        if initial_states.is_empty() {
+            return self.rejecting();
+        }
+        let mut nfa = self.clone().into_nfa();
+        nfa.initial_states = initial_states;
+        determine_and_minimize_nfa_with(nfa, Minimization::Hopcroft)
+    }
+
+    /// Minimal DFA accepting the words `v` such that `v` followed by a word
+    /// of `other` is accepted (right quotient `LK⁻¹`)
+    ///
+    /// The states of `self` reaching an accept state with a word accepted by
+    /// `other` become accepting, found by a backward search of the product of
+    /// the DFAs.
+    pub fn right_quotient_by(&self, other: &Self) -> Dfa<T> {
+        let mut explored = FxHashSet::default();
+        for accept_state in &self.accept_states {
+            for other_accept_state in &other.accept_states {
+                explored.insert((*accept_state, *other_accept_state));
+            }
+        }
+        let mut to_explore: Vec<(State, State)> = explored.iter().copied().collect();
+
+        let other_initial_state = other.initial_state();
+        let mut accept_states = FxHashSet::default();
+        while let Some((state, other_state)) = to_explore.pop() {
+            if other_state == other_initial_state {
+                accept_states.insert(state);
+            }
+            for link in self.links_to(state) {
+                for other_link in other.links_to(other_state) {
+                    let from = (link.from, other_link.from);
+                    if link.symbol == other_link.symbol && explored.insert(from) {
+                        to_explore.push(from);
+                    }
+                }
+            }
+        }
+
+        let mut dfa = self.clone();
+        dfa.accept_states = accept_states;
+        minimize_hopcroft(dfa)
+    }
+
+    /// State reached from `state` after `word`, if any
+    fn run(&self, state: State, word: &[T]) -> Option<State> {
+        word.iter().try_fold(state, |state, symbol| {
+            self.links_from(state)
+                .find(|link| link.symbol == *symbol)
+                .map(|link| link.to)
+        })
+    }
+
+    /// Minimal DFA of the empty language
+    fn rejecting(&self) -> Dfa<T> {
+        let mut dfa = self.clone();
+        dfa.accept_states.clear();
+        minimize_hopcroft(dfa)
+    }
+}
@@ -0,0 +1,116 @@
+use rand::Rng;
+use rustc_hash::FxHashMap;
+
+use crate::{words::completion_counts, *};
+
+impl<T: Clone> Dfa<T> {
+    /// Draw an accepted word of length `len` uniformly at random
+    ///
+    /// Each link is followed with a probability proportional to the number of
+    /// accepted words of the remaining length from its target, see
+    /// [Dfa::count_words]. Sampling is exactly uniform as long as these
+    /// counts fit in a `u128`. Returns `None` when no word has this length.
+    pub fn sample(&self, len: usize, rng: &mut impl Rng) -> Option<Vec<T>> {
+        let completions = completion_counts(self, len);
+
+        let mut state = self.initial_state();
+        let mut word = Vec::with_capacity(len);
+        for remaining in (0..len).rev() {
+            let total = *completions[remaining + 1].get(&state)?;
+            let mut pick = rng.random_range(0..total);
+
+            let link = self
+                .links_from(state)
+                .find(|link| {
+                    let count = completions[remaining].get(&link.to).copied().unwrap_or(0);
+                    if pick < count {
+                        return true;
+                    }
+                    pick -= count;
+                    false
+                })
+                .expect("Counts of the links should sum up to the count of their source");
+
+            word.push(link.symbol.clone());
+            state = link.to;
+        }
+
+        self.accept_states.contains(&state).then_some(word)
+    }
+
+    /// Draw an accepted word of any length, with a probability proportional
+    /// to `weight` to the power of its length (Boltzmann sampling)
+    ///
+    /// A smaller `weight` favours shorter words. It has to be lower than the
+    /// inverse of the growth rate of the language for the weights of all the
+    /// words to sum up: for instance below `1/2` for `[ab]*`. Returns `None`
+    /// when they don't, or when the language is empty.
+    pub fn sample_weighted(&self, weight: f64, rng: &mut impl Rng) -> Option<Vec<T>> {
+        const MAX_ITERATIONS: usize = 10_000;
+        const PRECISION: f64 = 1e-12;
+        assert!(weight >= 0.0, "Weight should not be negative");
+
+        // Sum of the weights of the words accepted from each state, computed
+        // as the limit of the sums over the words of at most `n` symbols
+        let mut sums: FxHashMap<State, f64> = Default::default();
+        let mut converged = false;
+        for _ in 0..MAX_ITERATIONS {
+            let mut next: FxHashMap<State, f64> = self
+                .accept_states
+                .iter()
+                .map(|state| (*state, 1.0))
+                .collect();
+            for link in self.links() {
+                if let Some(sum) = sums.get(&link.to) {
+                    *next.entry(link.from).or_default() += weight * sum;
+                }
+            }
+
+            let change = next
+                .iter()
+                .map(|(state, sum)| (sum - sums.get(state).unwrap_or(&0.0)) / sum)
+                .fold(0.0, f64::max);
+            sums = next;
+            if change < PRECISION {
+                converged = true;
+                break;
+            }
+        }
+
+        let mut state = self.initial_state();
+        let total = *sums.get(&state)?;
+        if !converged || !total.is_finite() {
+            return None;
+        }
+
+        let mut word = Vec::new();
+        loop {
+            let mut pick = rng.random::<f64>() * sums[&state];
+
+            if self.accept_states.contains(&state) {
+                pick -= 1.0;
+                if pick < 0.0 {
+                    return Some(word);
+                }
+            }
+
+            // Rounding errors may leave a bit of `pick`, the last link takes it
+            let mut links = self
+                .links_from(state)
+                .filter(|link| sums.contains_key(&link.to))
+                .peekable();
+            let link = loop {
+                let Some(link) = links.next() else {
+                    return self.accept_states.contains(&state).then_some(word);
+                };
+                pick -= weight * sums[&link.to];
+                if pick < 0.0 || links.peek().is_none() {
+                    break link;
+                }
+            };
+
+            word.push(link.symbol.clone());
+            state = link.to;
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::ops::Range;
+
+use regex_syntax::hir::{Class, ClassBytes, ClassBytesRange, Hir, Repetition};
+use rustc_hash::FxHashSet;
+
+use crate::{hir_to_nfa::compile_search, look::Margins, *};
+
+/// Location of a match in a haystack, `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Match {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl From<Match> for Range<usize> {
+    fn from(m: Match) -> Self {
+        m.range()
+    }
+}
+
+/// Unanchored REGEX search over bytes
+///
+/// A forward DFA of `(?s-u:.)*?` followed by the pattern finds where a match
+/// ends, a reverse DFA of the pattern, built by inverting its NFA, then walks
+/// back to the leftmost start of a match ending there. For leftmost-longest
+/// matches, an anchored forward DFA of the pattern finally extends the match
+/// from that start.
+///
+/// Unlike [execute_dfa], look-around assertions see the whole haystack: `^`
+/// only matches at its start and `\b` looks at the bytes around the match.
+#[derive(Debug, Clone)]
+pub struct Searcher {
+    kind: SearchKind,
+    forward: Direction,
+    /// Anchored at the start of the match, for leftmost-longest matches
+    longest: Option<Direction>,
+    reverse: Direction,
+}
+
+impl Searcher {
+    /// Leftmost-longest search, panics on unsupported syntax, see
+    /// [Searcher::try_new] for a fallible version.
+    pub fn new(hir: &Hir) -> Self {
+        Self::try_new(hir, &Config::default(), MatchKind::LeftmostLongest)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Search reporting the match selected by `kind`
+    ///
+    /// [MatchKind::LeftmostLongest] reports the longest of the matches
+    /// starting leftmost, [MatchKind::Earliest] the match ending first, the
+    /// longest among those ending there.
+    pub fn try_new(hir: &Hir, config: &Config, kind: MatchKind) -> Result<Self, BuildError> {
+        let kind = match kind {
+            MatchKind::LeftmostLongest => SearchKind::LeftmostLongest,
+            MatchKind::Earliest => SearchKind::Earliest,
+        };
+        Self::build(hir, config, kind)
+    }
+
+    /// Search reporting the match a backtracking engine would (Perl), the
+    /// one starting leftmost that follows the priorities of the REGEX, see
+    /// [determine_nfa_leftmost_first]
+    pub fn try_new_leftmost_first(hir: &Hir, config: &Config) -> Result<Self, BuildError> {
+        Self::build(hir, config, SearchKind::LeftmostFirst)
+    }
+
+    fn build(hir: &Hir, config: &Config, kind: SearchKind) -> Result<Self, BuildError> {
+        let any_byte = Hir::class(Class::Bytes(ClassBytes::new([ClassBytesRange::new(
+            u8::MIN,
+            u8::MAX,
+        )])));
+        let prefix = Hir::repetition(Repetition {
+            min: 0,
+            max: None,
+            greedy: false,
+            sub: Box::new(any_byte),
+        });
+
+        let (mut nfa, margins) = compile_search(&Hir::concat(vec![prefix, hir.clone()]), config)?;
+        // Leftmost-first determinization drops the matches starting after
+        // the leftmost one, and the matches of lower priority
+        let forward = match kind {
+            SearchKind::Earliest => Direction::forward(&mut nfa, &margins, determinize),
+            _ => Direction::forward(&mut nfa, &margins, determinize_leftmost_first),
+        };
+
+        let (mut nfa, margins) = compile_search(hir, config)?;
+        let longest = (kind == SearchKind::LeftmostLongest)
+            .then(|| Direction::forward(&mut nfa, &margins, determinize));
+
+        nfa.invert();
+        let Margins {
+            start,
+            inner_start,
+            inner_end,
+            end,
+        } = margins;
+        let reverse = Direction {
+            from_bound: SearchDfa::new(&mut nfa, end, inner_start, start, determinize),
+            from_inner: SearchDfa::new(&mut nfa, inner_end, inner_start, start, determinize),
+        };
+
+        Ok(Self {
+            kind,
+            forward,
+            longest,
+            reverse,
+        })
+    }
+
+    /// Find the first match of `haystack`, selected by the kind of search
+    pub fn find(&self, haystack: &[u8]) -> Option<Match> {
+        self.find_at(haystack, 0)
+    }
+
+    /// Same as [Searcher::find], for matches starting at `start` or after
+    ///
+    /// Bytes before `start` are still seen by look-around assertions.
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<Match> {
+        let earliest = self.kind == SearchKind::Earliest;
+        let end = self.forward.find_end(haystack, start, earliest)?;
+        let start = self.find_start(haystack, start, end);
+        let end = match &self.longest {
+            Some(longest) => longest.find_end(haystack, start, false).unwrap_or(end),
+            None => end,
+        };
+        Some(Match { start, end })
+    }
+
+    /// Iterate over the successive non-overlapping matches of `haystack`
+    pub fn find_iter<'s, 'h>(&'s self, haystack: &'h [u8]) -> FindIter<'s, 'h> {
+        FindIter {
+            searcher: self,
+            haystack,
+            at: 0,
+            last_end: None,
+        }
+    }
+
+    fn find_start(&self, haystack: &[u8], start: usize, end: usize) -> usize {
+        let (search, from) = self.reverse.at(end, haystack.len());
+        let mut state = search.dfa.initial_state();
+        let mut leftmost = end;
+
+        for idx in (start.saturating_sub(1)..from).rev() {
+            let Some(next) = search.next_state(state, haystack[idx]) else {
+                return leftmost;
+            };
+            state = next;
+            // The byte preceding the match has just been consumed
+            if search.dfa.accept_states.contains(&state) {
+                leftmost = idx + 1;
+            }
+        }
+
+        if start == 0 && search.at_bound.contains(&state) {
+            leftmost = 0;
+        }
+
+        leftmost
+    }
+}
+
+/// Match reported by a [Searcher]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchKind {
+    Earliest,
+    LeftmostLongest,
+    LeftmostFirst,
+}
+
+/// Iterator over the matches of a haystack, see [Searcher::find_iter]
+#[derive(Debug, Clone)]
+pub struct FindIter<'s, 'h> {
+    searcher: &'s Searcher,
+    haystack: &'h [u8],
+    at: usize,
+    /// End of the previous match
+    last_end: Option<usize>,
+}
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut found = self.searcher.find_at(self.haystack, self.at)?;
+        // An empty match right after the previous one is skipped
+        if found.is_empty() && self.last_end == Some(found.end) {
+            found = self.searcher.find_at(self.haystack, found.end + 1)?;
+        }
+        // Step over empty matches so that the search makes progress
+        self.at = if found.is_empty() {
+            found.end + 1
+        } else {
+            found.end
+        };
+        self.last_end = Some(found.end);
+        Some(found)
+    }
+}
+
+/// DFAs of a search direction
+#[derive(Debug, Clone)]
+struct Direction {
+    /// Started at the bound of the haystack the direction starts from
+    from_bound: SearchDfa,
+    /// Started one byte before the search position
+    from_inner: SearchDfa,
+}
+
+impl Direction {
+    /// Forward DFAs of a search NFA, see [compile_search]
+    fn forward(nfa: &mut Nfa<ByteRange>, margins: &Margins, determinize: Determinize) -> Self {
+        Self {
+            from_bound: SearchDfa::new(
+                nfa,
+                margins.start,
+                margins.inner_end,
+                margins.end,
+                determinize,
+            ),
+            from_inner: SearchDfa::new(
+                nfa,
+                margins.inner_start,
+                margins.inner_end,
+                margins.end,
+                determinize,
+            ),
+        }
+    }
+
+    /// Scan forward from `start`, returns the end of the first match when
+    /// `earliest`, of the last one before the DFA dies otherwise
+    fn find_end(&self, haystack: &[u8], start: usize, earliest: bool) -> Option<usize> {
+        if start > haystack.len() {
+            return None;
+        }
+
+        let (search, from) = self.at(start, 0);
+        let mut state = search.dfa.initial_state();
+        let mut last = None;
+
+        for (idx, byte) in haystack.iter().enumerate().skip(from) {
+            let Some(next) = search.next_state(state, *byte) else {
+                return last;
+            };
+            state = next;
+            // The byte following the match has just been consumed
+            if search.dfa.accept_states.contains(&state) {
+                if earliest {
+                    return Some(idx);
+                }
+                last = Some(idx);
+            }
+        }
+
+        if search.at_bound.contains(&state) {
+            Some(haystack.len())
+        } else {
+            last
+        }
+    }
+
+    /// DFA to start a search at `position`, along with where its scan starts,
+    /// `bound` being the end of the haystack the direction starts from
+    fn at(&self, position: usize, bound: usize) -> (&SearchDfa, usize) {
+        match position.cmp(&bound) {
+            std::cmp::Ordering::Equal => (&self.from_bound, position),
+            std::cmp::Ordering::Greater => (&self.from_inner, position - 1),
+            std::cmp::Ordering::Less => (&self.from_inner, position + 1),
+        }
+    }
+}
+
+/// DFA of a search, accept states are reached by consuming the byte following
+/// a match
+#[derive(Debug, Clone)]
+struct SearchDfa {
+    dfa: Dfa<ByteRange>,
+    /// States where a match ends at the bound of the haystack
+    at_bound: FxHashSet<State>,
+}
+
+/// Subset construction of a search DFA
+type Determinize = fn(&Nfa<ByteRange>) -> Determinized<ByteRange>;
+
+impl SearchDfa {
+    fn new(
+        nfa: &mut Nfa<ByteRange>,
+        initial: State,
+        accept: State,
+        bound: State,
+        determinize: Determinize,
+    ) -> Self {
+        nfa.initial_states = [initial].into_iter().collect();
+        nfa.accept_states = [accept].into_iter().collect();
+
+        let Determinized {
+            mut dfa,
+            nfa_states,
+        } = determinize(nfa);
+        dfa.merge_ranges();
+
+        let at_bound = nfa_states
+            .into_iter()
+            .filter(|(_, nfa_states)| nfa_states.binary_search(&bound).is_ok())
+            .map(|(state, _)| state)
+            .collect();
+
+        Self { dfa, at_bound }
+    }
+
+    fn next_state(&self, state: State, byte: u8) -> Option<State> {
+        self.dfa
+            .links_from(state)
+            .find(|link| link.symbol.contains(byte))
+            .map(|link| link.to)
+    }
+}
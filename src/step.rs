@@ -0,0 +1,96 @@
+use rustc_hash::FxHashSet;
+
+use crate::*;
+
+/// Status of a partial input, see [Stepper]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+    /// The input read so far is accepted
+    Accepted,
+    /// The input is not accepted, but some extension of it is
+    Live,
+    /// No extension of the input can be accepted
+    Dead,
+}
+
+/// Incremental DFA executor, reading the input one item at a time
+///
+/// The live states, the ones an accept state is reachable from, are computed
+/// once so that each step tells apart a partial input that can still be
+/// accepted from a dead one. Call [Stepper::reset] to reuse them for another
+/// input.
+#[derive(Debug, Clone)]
+pub struct Stepper<'a, T> {
+    dfa: &'a Dfa<T>,
+    live: FxHashSet<State>,
+    /// `None` once the input is dead
+    state: Option<State>,
+}
+
+impl<'a, T> Stepper<'a, T> {
+    pub fn new(dfa: &'a Dfa<T>) -> Self {
+        let mut stepper = Self {
+            dfa,
+            live: dfa.coreachable_states(),
+            state: None,
+        };
+        stepper.reset();
+        stepper
+    }
+
+    /// Read one more item
+    pub fn step<I>(&mut self, item: &I) -> StepResult
+    where
+        T: Matches<I>,
+    {
+        if let Some(state) = self.state {
+            self.state = self
+                .dfa
+                .links_from(state)
+                .find(|link| link.symbol.matches(item))
+                .map(|link| link.to)
+                .filter(|to| self.live.contains(to));
+        }
+        self.result()
+    }
+
+    /// Read every item of `items`, stopping early once the input is dead
+    pub fn step_all<I>(&mut self, items: &[I]) -> StepResult
+    where
+        T: Matches<I>,
+    {
+        for item in items {
+            if self.step(item) == StepResult::Dead {
+                break;
+            }
+        }
+        self.result()
+    }
+
+    /// Status of the input read so far
+    pub fn result(&self) -> StepResult {
+        match self.state {
+            Some(state) if self.dfa.accept_states.contains(&state) => StepResult::Accepted,
+            Some(_) => StepResult::Live,
+            None => StepResult::Dead,
+        }
+    }
+
+    /// Current state, `None` once the input is dead
+    pub fn state(&self) -> Option<State> {
+        self.state
+    }
+
+    /// Start over with an empty input
+    pub fn reset(&mut self) {
+        let initial_state = self.dfa.initial_state();
+        self.state = self.live.contains(&initial_state).then_some(initial_state);
+    }
+}
+
+impl<T> Dfa<T> {
+    /// Incremental executor of this DFA, see [Stepper]
+    pub fn stepper(&self) -> Stepper<'_, T> {
+        Stepper::new(self)
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, BufReader, Read},
+};
+
+use rustc_hash::FxHashSet;
+
+use crate::*;
+
+/// Resumable DFA executor, reading the haystack chunk by chunk
+///
+/// Reports the same match as [execute_dfa_with] would on the concatenation of
+/// the chunks, its end being an offset from the start of the first chunk.
+///
+/// As with [Stepper], the live states, the ones an accept state is reachable
+/// from, are computed once so that the matcher is done as soon as it reaches
+/// a dead state, even one of a complete DFA. Matchers of a same DFA can share
+/// them, see [Matcher::with_live_states].
+#[derive(Debug, Clone)]
+pub struct Matcher<'a, T> {
+    dfa: &'a Dfa<T>,
+    kind: MatchKind,
+    /// `None` when only a missing link stops the matcher
+    live: Option<Cow<'a, FxHashSet<State>>>,
+    state: State,
+    /// Number of items read so far
+    offset: usize,
+    last_match: Option<usize>,
+    /// No more input can change the match
+    done: bool,
+}
+
+impl<'a, T> Matcher<'a, T> {
+    pub fn new(dfa: &'a Dfa<T>, kind: MatchKind) -> Self {
+        Self::with_live(dfa, kind, Some(Cow::Owned(dfa.coreachable_states())))
+    }
+
+    /// Same as [Matcher::new], with the live states of `dfa` computed
+    /// beforehand by [coreachable_states](Automata::coreachable_states)
+    pub fn with_live_states(dfa: &'a Dfa<T>, kind: MatchKind, live: &'a FxHashSet<State>) -> Self {
+        Self::with_live(dfa, kind, Some(Cow::Borrowed(live)))
+    }
+
+    /// Same as [Matcher::new], only stopping on a missing link
+    ///
+    /// Cheaper when the whole haystack is fed at once.
+    pub(crate) fn without_live_states(dfa: &'a Dfa<T>, kind: MatchKind) -> Self {
+        Self::with_live(dfa, kind, None)
+    }
+
+    fn with_live(
+        dfa: &'a Dfa<T>,
+        kind: MatchKind,
+        live: Option<Cow<'a, FxHashSet<State>>>,
+    ) -> Self {
+        let state = dfa.initial_state();
+        Self {
+            dfa,
+            kind,
+            done: live.as_ref().is_some_and(|live| !live.contains(&state)),
+            live,
+            state,
+            offset: 0,
+            last_match: None,
+        }
+    }
+
+    /// Read the next chunk of the haystack
+    ///
+    /// Returns `Some` once the match is known for sure, whatever comes next,
+    /// holding its end or `None` when there is no match. The rest of the chunk
+    /// is ignored then, like any chunk fed after it, see [Matcher::is_done].
+    pub fn feed<I>(&mut self, chunk: &[I]) -> Option<Option<usize>>
+    where
+        T: Matches<I>,
+    {
+        if self.done {
+            return Some(self.last_match);
+        }
+
+        for item in chunk {
+            if self.dfa.accept_states.contains(&self.state) {
+                self.last_match = Some(self.offset);
+                if self.kind == MatchKind::Earliest {
+                    self.done = true;
+                    return Some(self.last_match);
+                }
+            }
+
+            let to = self
+                .dfa
+                .links_from(self.state)
+                .find(|link| link.symbol.matches(item))
+                .map(|link| link.to);
+            let live = |to: &State| self.live.as_ref().is_none_or(|live| live.contains(to));
+            let Some(to) = to.filter(live) else {
+                self.done = true;
+                return Some(self.last_match);
+            };
+            self.state = to;
+            self.offset += 1;
+        }
+
+        None
+    }
+
+    /// End of the haystack, returns the end of the match if any
+    pub fn finish(self) -> Option<usize> {
+        if !self.done && self.dfa.accept_states.contains(&self.state) {
+            Some(self.offset)
+        } else {
+            self.last_match
+        }
+    }
+
+    /// Check if the match is known for sure, or that there is none, so that
+    /// the rest of the haystack doesn't need to be fed
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Number of items read so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Same as [execute_dfa_with], reading the haystack from `reader` until the
+/// match is known or the end of the input
+///
+/// Reading stops at the first dead state, so an endless `reader` is only read
+/// forever while the input read so far can still be extended to a match.
+pub fn execute_buf_read<T>(
+    dfa: &Dfa<T>,
+    mut reader: impl BufRead,
+    kind: MatchKind,
+) -> io::Result<Option<usize>>
+where
+    T: Matches<u8>,
+{
+    let mut matcher = Matcher::new(dfa, kind);
+    while !matcher.is_done() {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+        let len = chunk.len();
+        matcher.feed(chunk);
+        reader.consume(len);
+    }
+    Ok(matcher.finish())
+}
+
+/// Same as [execute_buf_read], buffering `reader`
+pub fn execute_read<T>(
+    dfa: &Dfa<T>,
+    reader: impl Read,
+    kind: MatchKind,
+) -> io::Result<Option<usize>>
+where
+    T: Matches<u8>,
+{
+    execute_buf_read(dfa, BufReader::new(reader), kind)
+}
@@ -0,0 +1,76 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::*;
+
+impl<T> Automata<T> {
+    /// States reachable from the initial states
+    pub fn reachable_states(&self) -> FxHashSet<State> {
+        let mut to_explore: Vec<State> = self.initial_states.iter().copied().collect();
+        let mut reachable = FxHashSet::default();
+
+        while let Some(state) = to_explore.pop() {
+            if reachable.insert(state) {
+                to_explore.extend(self.links_from(state).map(|link| link.to));
+            }
+        }
+
+        reachable
+    }
+
+    /// States an accept state is reachable from
+    pub fn coreachable_states(&self) -> FxHashSet<State> {
+        let mut to_explore: Vec<State> = self.accept_states.iter().copied().collect();
+        let mut coreachable = FxHashSet::default();
+
+        while let Some(state) = to_explore.pop() {
+            if coreachable.insert(state) {
+                to_explore.extend(self.links_to(state).map(|link| link.from));
+            }
+        }
+
+        coreachable
+    }
+
+    /// States both reachable and coreachable, the ones on a path from an
+    /// initial state to an accept state
+    pub fn useful_states(&self) -> FxHashSet<State> {
+        let reachable = self.reachable_states();
+        self.coreachable_states()
+            .into_iter()
+            .filter(|state| reachable.contains(state))
+            .collect()
+    }
+
+    /// Remove the states that are not both reachable and coreachable, along
+    /// with their links
+    ///
+    /// Initial states are kept even when useless, so that a DFA of the empty
+    /// language still has its initial state.
+    pub fn trim(&mut self) {
+        let useful = self.useful_states();
+        self.accept_states.retain(|state| useful.contains(state));
+        self.retain_links(|link| useful.contains(&link.from) && useful.contains(&link.to));
+    }
+
+    /// Renumber the states to `0..n`, following their order
+    ///
+    /// Returns the new number of each state.
+    pub fn renumber(&mut self) -> FxHashMap<State, State> {
+        let mut states: Vec<State> = self.states_set().into_iter().collect();
+        states.sort();
+
+        let counter = Counter::new(0);
+        let renumbered: FxHashMap<State, State> = states
+            .into_iter()
+            .map(|state| (state, counter.next()))
+            .collect();
+
+        self.initial_states = self.initial_states.iter().map(|s| renumbered[s]).collect();
+        self.accept_states = self.accept_states.iter().map(|s| renumbered[s]).collect();
+        for link in self.take_links() {
+            self.link(renumbered[&link.from], renumbered[&link.to], link.symbol);
+        }
+
+        renumbered
+    }
+}
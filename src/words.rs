@@ -0,0 +1,290 @@
+use std::{
+    collections::{VecDeque, hash_map::Entry},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::*;
+
+impl<T> Dfa<T>
+where
+    T: Ord + Hash + Clone,
+{
+    /// Shortest accepted word, the lexicographically smallest one when several
+    /// have the same length
+    ///
+    /// Breadth-first search of the states, following links by increasing
+    /// symbol.
+    pub fn shortest_word(&self) -> Option<Vec<T>> {
+        let initial_state = self.initial_state();
+        // Link each state was first reached with
+        let mut parents: FxHashMap<State, Option<&DfaLink<T>>> = Default::default();
+        parents.insert(initial_state, None);
+        let mut to_explore = VecDeque::from([initial_state]);
+
+        while let Some(state) = to_explore.pop_front() {
+            if self.accept_states.contains(&state) {
+                let mut word = Vec::new();
+                let mut current = state;
+                while let Some(link) = parents[&current] {
+                    word.push(link.symbol.clone());
+                    current = link.from;
+                }
+                word.reverse();
+                return Some(word);
+            }
+
+            for link in self.sorted_links_from(state) {
+                if let Entry::Vacant(entry) = parents.entry(link.to) {
+                    entry.insert(Some(link));
+                    to_explore.push_back(link.to);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Lexicographically smallest accepted word of length `len`
+    pub fn smallest_word_of_len(&self, len: usize) -> Option<Vec<T>> {
+        let completions = completion_counts(self, len);
+
+        let mut state = self.initial_state();
+        if !completions[len].contains_key(&state) {
+            return None;
+        }
+
+        let mut word = Vec::with_capacity(len);
+        for remaining in (0..len).rev() {
+            let link = self
+                .sorted_links_from(state)
+                .into_iter()
+                .find(|link| completions[remaining].contains_key(&link.to))?;
+            word.push(link.symbol.clone());
+            state = link.to;
+        }
+
+        Some(word)
+    }
+
+    /// The `count` first accepted words in shortlex order: by length, then
+    /// lexicographically
+    ///
+    /// Words are generated length by length, extending only the prefixes that
+    /// can be completed to an accepted word of the current length, so each
+    /// explored prefix leads to a returned word.
+    pub fn shortest_words(&self, count: usize) -> Vec<Vec<T>> {
+        // Without a cycle, accepted words are shorter than the number of states
+        let max_len = self.is_finite().then(|| self.states_set().len());
+        let initial_state = self.initial_state();
+        let mut words = Vec::new();
+
+        let mut completions = completion_counts(self, 0);
+        while words.len() < count && max_len.is_none_or(|max_len| completions.len() <= max_len) {
+            let len = completions.len() - 1;
+            if completions[len].contains_key(&initial_state) {
+                // Depth-first search of the prefixes, in lexicographic order
+                let mut to_explore = vec![(initial_state, Vec::new())];
+                while let Some((state, word)) = to_explore.pop() {
+                    if word.len() == len {
+                        words.push(word);
+                        if words.len() == count {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    let remaining = len - word.len() - 1;
+                    for link in self.sorted_links_from(state).into_iter().rev() {
+                        if completions[remaining].contains_key(&link.to) {
+                            let mut next = word.clone();
+                            next.push(link.symbol.clone());
+                            to_explore.push((link.to, next));
+                        }
+                    }
+                }
+            }
+
+            completions.push(previous_completion_counts(self, &completions[len]));
+        }
+
+        words
+    }
+}
+
+impl<T> Dfa<T>
+where
+    T: Ord + Clone,
+{
+    /// Iterate over the accepted words of at most `max_len` symbols, in
+    /// shortlex order: by length, then lexicographically
+    ///
+    /// Paths are explored breadth-first, skipping the ones that can't reach an
+    /// accept state within `max_len` symbols.
+    pub fn words(&self, max_len: usize) -> Words<'_, T> {
+        // Length of the shortest path from each state to an accept state
+        let mut distances: FxHashMap<State, usize> = Default::default();
+        let mut to_explore: VecDeque<State> = self.accept_states.iter().copied().collect();
+        for state in &self.accept_states {
+            distances.insert(*state, 0);
+        }
+        while let Some(state) = to_explore.pop_front() {
+            let distance = distances[&state] + 1;
+            for link in self.links_to(state) {
+                if let Entry::Vacant(entry) = distances.entry(link.from) {
+                    entry.insert(distance);
+                    to_explore.push_back(link.from);
+                }
+            }
+        }
+
+        let mut words = Words {
+            dfa: self,
+            max_len,
+            distances,
+            to_explore: VecDeque::new(),
+        };
+        words.push(self.initial_state(), Vec::new());
+        words
+    }
+
+    fn sorted_links_from(&self, state: State) -> Vec<&DfaLink<T>> {
+        let mut links: Vec<_> = self.links_from(state).collect();
+        links.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        links
+    }
+}
+
+impl<T> Dfa<T> {
+    /// Number of accepted words of length `len`, see [Dfa::count_up_to]
+    pub fn count_words(&self, len: usize) -> u128 {
+        self.count_up_to(len)[len]
+    }
+
+    /// Number of accepted words of each length from `0` to `max_len`
+    /// included, the first coefficients of the generating function of the
+    /// language
+    ///
+    /// Dynamic programming over the links, counting the paths reaching each
+    /// state. Each link stands for a single symbol. Counts saturate at
+    /// [u128::MAX].
+    pub fn count_up_to(&self, max_len: usize) -> Vec<u128> {
+        let mut paths: FxHashMap<State, u128> = FxHashMap::from_iter([(self.initial_state(), 1)]);
+        let mut counts = Vec::with_capacity(max_len + 1);
+
+        loop {
+            let accepted = paths
+                .iter()
+                .filter(|(state, _)| self.accept_states.contains(state))
+                .fold(0u128, |total, (_, count)| total.saturating_add(*count));
+            counts.push(accepted);
+            if counts.len() > max_len {
+                return counts;
+            }
+
+            let mut next: FxHashMap<State, u128> = Default::default();
+            for (state, count) in &paths {
+                for link in self.links_from(*state) {
+                    let total = next.entry(link.to).or_default();
+                    *total = total.saturating_add(*count);
+                }
+            }
+            paths = next;
+        }
+    }
+}
+
+/// `counts[n][state]`: number of accepted words of length `n` from `state`,
+/// for `n` up to `len` included
+///
+/// States without such a word are left out. Counts saturate at [u128::MAX].
+pub(crate) fn completion_counts<T>(dfa: &Dfa<T>, len: usize) -> Vec<FxHashMap<State, u128>> {
+    let mut counts = vec![dfa.accept_states.iter().map(|state| (*state, 1)).collect()];
+    for _ in 0..len {
+        counts.push(previous_completion_counts(dfa, counts.last().unwrap()));
+    }
+    counts
+}
+
+/// Completion counts for one more symbol than `counts`, see [completion_counts]
+fn previous_completion_counts<T>(
+    dfa: &Dfa<T>,
+    counts: &FxHashMap<State, u128>,
+) -> FxHashMap<State, u128> {
+    let mut previous: FxHashMap<State, u128> = Default::default();
+    for (state, count) in counts {
+        for link in dfa.links_to(*state) {
+            let total = previous.entry(link.from).or_default();
+            *total = total.saturating_add(*count);
+        }
+    }
+    previous
+}
+
+/// Iterator over the accepted words of a DFA, see [Dfa::words]
+#[derive(Debug, Clone)]
+pub struct Words<'a, T> {
+    dfa: &'a Dfa<T>,
+    max_len: usize,
+    distances: FxHashMap<State, usize>,
+    /// Paths to extend, in shortlex order
+    to_explore: VecDeque<(State, Vec<T>)>,
+}
+
+impl<T> Words<'_, T> {
+    /// Queue the path unless no accepted word of at most `max_len` extends it
+    fn push(&mut self, state: State, word: Vec<T>) {
+        if let Some(distance) = self.distances.get(&state)
+            && word.len() + distance <= self.max_len
+        {
+            self.to_explore.push_back((state, word));
+        }
+    }
+}
+
+impl<T> Iterator for Words<'_, T>
+where
+    T: Ord + Clone,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((state, word)) = self.to_explore.pop_front() {
+            if word.len() < self.max_len {
+                for link in self.dfa.sorted_links_from(state) {
+                    let mut next = word.clone();
+                    next.push(link.symbol.clone());
+                    self.push(link.to, next);
+                }
+            }
+
+            if self.dfa.accept_states.contains(&state) {
+                return Some(word);
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Nfa<T>
+where
+    T: Ord + Hash + Clone + Debug,
+{
+    /// Same as [Dfa::shortest_word], the NFA is determinized first
+    pub fn shortest_word_nfa(&self) -> Option<Vec<T>> {
+        determine_nfa(self).shortest_word()
+    }
+
+    /// Same as [Dfa::smallest_word_of_len], the NFA is determinized first
+    pub fn smallest_word_of_len_nfa(&self, len: usize) -> Option<Vec<T>> {
+        determine_nfa(self).smallest_word_of_len(len)
+    }
+
+    /// Same as [Dfa::shortest_words], the NFA is determinized first
+    pub fn shortest_words_nfa(&self, count: usize) -> Vec<Vec<T>> {
+        determine_nfa(self).shortest_words(count)
+    }
+}
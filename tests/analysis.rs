@@ -0,0 +1,63 @@
+use regex_automata::determine_nfa;
+
+mod common;
+
+use common::{dfa, nfa};
+
+#[test]
+fn empty() {
+    for pattern in ["a", "", "a*", "[a&&b]|c"] {
+        assert!(!nfa(pattern).is_empty_nfa(), "{pattern}");
+        assert!(!dfa(pattern).is_empty(), "{pattern}");
+    }
+    for pattern in ["[a&&b]", "a[a&&b]*b[a&&b]", r"a\Ab"] {
+        assert!(nfa(pattern).is_empty_nfa(), "{pattern}");
+        assert!(dfa(pattern).is_empty(), "{pattern}");
+    }
+}
+
+#[test]
+fn finite() {
+    for pattern in ["a", "", "a{2,5}|b", "a[a&&b]*"] {
+        assert!(nfa(pattern).is_finite_nfa(), "{pattern}");
+        assert!(dfa(pattern).is_finite(), "{pattern}");
+    }
+    for pattern in ["a+", "(?:ab)*c", "a(?:b|c)*", "(?:a?)*"] {
+        assert!(!nfa(pattern).is_finite_nfa(), "{pattern}");
+        assert!(!dfa(pattern).is_finite(), "{pattern}");
+    }
+
+    // Epsilon cycles don't repeat any symbol
+    assert!(nfa("(?:)*").is_finite_nfa());
+    assert!(determine_nfa(&nfa("(?:)*")).is_finite());
+}
+
+#[test]
+fn universal() {
+    let alphabet = b"ab";
+    for pattern in ["[ab]*", "(?:a|b*)*", "|a[ab]*|b[ab]*"] {
+        assert!(nfa(pattern).is_universal_nfa(alphabet), "{pattern}");
+        assert!(dfa(pattern).is_universal(alphabet), "{pattern}");
+    }
+    for pattern in ["[ab]+", "a*", "(?:ab)*", "[ab]*a|"] {
+        assert!(!nfa(pattern).is_universal_nfa(alphabet), "{pattern}");
+        assert!(!dfa(pattern).is_universal(alphabet), "{pattern}");
+    }
+    assert!(!dfa("[ab]*").is_universal(b"abc"));
+
+    // Links outside the alphabet don't matter
+    assert!(dfa("[ab]*|cd").is_universal(alphabet));
+    assert!(nfa("[ab]*|cd").is_universal_nfa(alphabet));
+}
+
+#[test]
+fn empty_string() {
+    for pattern in ["", "a*", "a?b?", "^$"] {
+        assert!(nfa(pattern).accepts_empty_string_nfa(), "{pattern}");
+        assert!(dfa(pattern).accepts_empty_string(), "{pattern}");
+    }
+    for pattern in ["a", "a+", "a?b"] {
+        assert!(!nfa(pattern).accepts_empty_string_nfa(), "{pattern}");
+        assert!(!dfa(pattern).accepts_empty_string(), "{pattern}");
+    }
+}
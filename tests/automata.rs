@@ -0,0 +1,78 @@
+use regex_automata::{Dfa, MaybeSymbol, Nfa, determine_nfa, execute_dfa};
+
+fn sorted_targets(dfa: &Dfa<char>, from: u32) -> Vec<(u32, char)> {
+    let mut targets: Vec<_> = dfa.links_from(from).map(|l| (l.to, l.symbol)).collect();
+    targets.sort();
+    targets
+}
+
+#[test]
+fn index_follows_edits() {
+    let mut dfa = Dfa::new();
+    dfa.initial_states.insert(0);
+    dfa.accept_states.insert(2);
+    dfa.link(0, 1, 'a');
+    dfa.link(0, 2, 'b');
+    dfa.link(1, 2, 'c');
+
+    assert_eq!(sorted_targets(&dfa, 0), vec![(1, 'a'), (2, 'b')]);
+    assert_eq!(dfa.links_to(2).count(), 2);
+    assert_eq!(dfa.links_from_to(1, 2).count(), 1);
+
+    dfa.remove_links(0, 1);
+    assert_eq!(sorted_targets(&dfa, 0), vec![(2, 'b')]);
+    assert_eq!(dfa.links_from(1).count(), 1);
+
+    dfa.invert();
+    assert_eq!(dfa.links_from(2).count(), 2);
+    assert_eq!(dfa.links_to(0).count(), 1);
+
+    dfa.remove_links_any(2);
+    assert_eq!(dfa.link_count(), 0);
+    assert_eq!(dfa.links_from(2).count(), 0);
+}
+
+#[test]
+fn index_after_removals() {
+    let mut dfa = Dfa::new();
+    for state in 0..10 {
+        dfa.link(state, state + 1, 'a');
+        dfa.link(state, state + 2, 'b');
+    }
+
+    // Enough removals to compact the links
+    for state in 0..8 {
+        dfa.remove_links_any(state);
+    }
+    assert_eq!(dfa.link_count(), 4);
+    assert_eq!(sorted_targets(&dfa, 9), vec![(10, 'a'), (11, 'b')]);
+    assert_eq!(
+        dfa.links_to(10).map(|l| l.from).collect::<Vec<_>>(),
+        vec![8, 9]
+    );
+
+    dfa.retain_links(|link| link.symbol == 'a');
+    assert_eq!(sorted_targets(&dfa, 8), vec![(9, 'a')]);
+    assert_eq!(sorted_targets(&dfa, 9), vec![(10, 'a')]);
+    assert_eq!(dfa.links().count(), 2);
+}
+
+#[test]
+fn determine_large_nfa() {
+    const LEN: u32 = 100_000;
+
+    let mut nfa = Nfa::new();
+    nfa.initial_states.insert(0);
+    nfa.accept_states.insert(LEN);
+    for state in 0..LEN {
+        nfa.link(state, state + 1, MaybeSymbol::Symbol(state % 2 == 0));
+        if state % 1000 == 0 {
+            nfa.link(state, state + 1, MaybeSymbol::Epsilon);
+        }
+    }
+
+    let dfa = determine_nfa(&nfa);
+    let haystack: Vec<bool> = (0..LEN).map(|state| state % 2 == 0).collect();
+    assert_eq!(execute_dfa(&dfa, &haystack), Some(LEN as usize));
+    assert!(execute_dfa(&dfa, &[true, true]).is_none());
+}
@@ -0,0 +1,65 @@
+use regex_automata::{MaybeSymbol, Nfa, determine_nfa};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Token {
+    Let,
+    Ident,
+    Eq,
+}
+
+use Token::*;
+
+fn token(token: Token) -> Nfa<Token> {
+    let mut nfa = Nfa::new();
+    nfa.initial_states.insert(0);
+    nfa.accept_states.insert(1);
+    nfa.link(0, 1, MaybeSymbol::Symbol(token));
+    nfa
+}
+
+/// Accepted words of at most `max_len` tokens, in shortlex order
+fn words(nfa: &Nfa<Token>, max_len: usize) -> Vec<Vec<Token>> {
+    determine_nfa(nfa).words(max_len).collect()
+}
+
+#[test]
+fn concat_and_union() {
+    let statement = token(Let).concat(&token(Ident)).concat(&token(Eq));
+    assert_eq!(words(&statement, 5), [vec![Let, Ident, Eq]]);
+
+    let either = token(Let).union_nfa(&token(Ident));
+    assert_eq!(words(&either, 5), [vec![Let], vec![Ident]]);
+}
+
+#[test]
+fn star_plus_optional() {
+    let ident = token(Ident);
+    assert_eq!(
+        words(&ident.star(), 2),
+        [vec![], vec![Ident], vec![Ident, Ident]]
+    );
+    assert_eq!(words(&ident.plus(), 2), [vec![Ident], vec![Ident, Ident]]);
+    assert_eq!(words(&ident.optional(), 2), [vec![], vec![Ident]]);
+    assert_eq!(words(&Nfa::empty_string(), 2), [Vec::<Token>::new()]);
+}
+
+#[test]
+fn repeat() {
+    let ident = token(Ident);
+    let lengths =
+        |nfa: &Nfa<Token>| -> Vec<usize> { words(nfa, 5).iter().map(|word| word.len()).collect() };
+
+    assert_eq!(lengths(&ident.repeat(2, Some(3))), [2, 3]);
+    assert_eq!(lengths(&ident.repeat(0, Some(0))), [0]);
+    assert_eq!(lengths(&ident.repeat(3, None)), [3, 4, 5]);
+    assert_eq!(lengths(&ident.repeat(0, Some(2))), [0, 1, 2]);
+}
+
+#[test]
+fn reverse() {
+    let statement = token(Let).concat(&token(Ident).plus());
+    assert_eq!(
+        words(&statement.reverse(), 3),
+        [vec![Ident, Let], vec![Ident, Ident, Let]]
+    );
+}
@@ -0,0 +1,48 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use regex_automata::{
+    ByteRange, Config, Dfa, MatchKind, Matches, Nfa, determine_and_minimize_nfa, execute_dfa_with,
+    try_hir_to_nfa, try_hir_to_range_nfa,
+};
+use regex_syntax::{ParserBuilder, hir::Hir};
+
+fn parse(pattern: &str) -> Hir {
+    ParserBuilder::new()
+        .utf8(false)
+        .build()
+        .parse(pattern)
+        .unwrap()
+}
+
+/// Assertions may be checked at the end of a match, automata of the helpers
+/// run on whole inputs
+pub const WHOLE_INPUT: Config = Config {
+    size_limit: None,
+    whole_input: true,
+};
+
+/// NFA of `pattern`, which may match invalid UTF-8 with `(?-u)`
+pub fn nfa(pattern: &str) -> Nfa<u8> {
+    try_hir_to_nfa(&parse(pattern), &WHOLE_INPUT).unwrap()
+}
+
+/// Same as [nfa], over byte ranges
+pub fn range_nfa(pattern: &str) -> Nfa<ByteRange> {
+    try_hir_to_range_nfa(&parse(pattern), &WHOLE_INPUT).unwrap()
+}
+
+/// Minimal DFA of `pattern`
+pub fn dfa(pattern: &str) -> Dfa<u8> {
+    determine_and_minimize_nfa(nfa(pattern))
+}
+
+/// Same as [dfa], over byte ranges
+pub fn range_dfa(pattern: &str) -> Dfa<ByteRange> {
+    determine_and_minimize_nfa(range_nfa(pattern))
+}
+
+/// Check if `dfa` accepts the whole `haystack`
+pub fn accepts<T: Matches<u8>>(dfa: &Dfa<T>, haystack: &[u8]) -> bool {
+    execute_dfa_with(dfa, haystack, MatchKind::LeftmostLongest) == Some(haystack.len())
+}
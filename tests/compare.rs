@@ -0,0 +1,65 @@
+use regex_automata::{determine_nfa, equivalent, hir_to_nfa, is_subset};
+use regex_syntax::parse;
+
+mod common;
+
+use common::{dfa, nfa, range_dfa, range_nfa};
+
+#[test]
+fn equivalent_languages() {
+    assert_eq!(equivalent(&dfa("(a|b)*"), &dfa("(a*b*)*")), Ok(()));
+    assert_eq!(equivalent(&dfa("a+"), &dfa("aa*")), Ok(()));
+
+    // Not minimized on one side
+    let nfa = hir_to_nfa(&parse("(?:ab|ac)*").unwrap());
+    assert_eq!(equivalent(&determine_nfa(&nfa), &dfa("(?:a[bc])*")), Ok(()));
+}
+
+#[test]
+fn shortest_counterexample() {
+    assert_eq!(equivalent(&dfa("a*"), &dfa("a+")), Err(vec![]));
+    assert_eq!(
+        equivalent(&dfa("a{0,3}"), &dfa("a{0,4}")),
+        Err(b"aaaa".to_vec())
+    );
+    assert_eq!(equivalent(&dfa("ab|cd"), &dfa("ab")), Err(b"cd".to_vec()));
+    assert_eq!(
+        equivalent(&dfa("(?:ab)*"), &dfa("(?:ab)*|abababc")),
+        Err(b"abababc".to_vec())
+    );
+}
+
+#[test]
+fn subset() {
+    assert_eq!(is_subset(&nfa("ab+"), &nfa("[a-z]+")), Ok(()));
+    assert_eq!(is_subset(&nfa("(?:ab)*"), &nfa("(?:a|b)*")), Ok(()));
+    assert_eq!(is_subset(&nfa("a|b"), &nfa("b|a")), Ok(()));
+    assert_eq!(is_subset(&nfa("a{2,3}"), &nfa("a+")), Ok(()));
+}
+
+#[test]
+fn subset_witness() {
+    assert_eq!(is_subset(&nfa("[a-z]+"), &nfa("ab+")), Err(b"a".to_vec()));
+    assert_eq!(is_subset(&nfa("a*"), &nfa("a+")), Err(vec![]));
+    assert_eq!(
+        is_subset(&nfa("(?:a|b)*"), &nfa("(?:a|b)*a(?:a|b)*|b{0,2}")),
+        Err(b"bbb".to_vec())
+    );
+    assert_eq!(
+        is_subset(&nfa("[0-9]{3}-[0-9]{4}"), &nfa("[0-9]{3}-[0-8][0-9]{3}")),
+        Err(b"000-9000".to_vec())
+    );
+}
+
+#[test]
+fn ranges_split_differently() {
+    let split = range_dfa("[a-c][xy]|[d-z][xy]");
+    assert_eq!(equivalent(&range_dfa("[a-z][xy]"), &split), Ok(()));
+    assert!(equivalent(&range_dfa("[a-y][xy]"), &split).is_err());
+
+    assert_eq!(
+        is_subset(&range_nfa("[b-e]x"), &range_nfa("[a-c]x|[d-z]x")),
+        Ok(())
+    );
+    assert!(is_subset(&range_nfa("[a-c]x|[d-z]x"), &range_nfa("[b-e]x")).is_err());
+}
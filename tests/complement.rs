@@ -0,0 +1,65 @@
+use regex_automata::dfa_to_hir;
+use regex_syntax::hir::Hir;
+
+mod common;
+
+use common::{accepts, dfa};
+
+#[test]
+fn complete() {
+    let mut ab = dfa("ab");
+    let states = ab.states_set().len();
+
+    ab.complete(b"ab");
+    assert_eq!(ab.states_set().len(), states + 1);
+    for state in ab.states_set() {
+        assert_eq!(ab.links_from(state).count(), 2);
+    }
+    assert!(accepts(&ab, b"ab"));
+    assert!(!accepts(&ab, b"ba"));
+
+    // Already complete
+    let links = ab.link_count();
+    ab.complete(b"ab");
+    assert_eq!(ab.link_count(), links);
+}
+
+#[test]
+fn complement_over_alphabet() {
+    let even = dfa("(?:aa)*");
+    let odd = even.complement(b"a");
+
+    assert!(accepts(&odd, b"a"));
+    assert!(accepts(&odd, b"aaa"));
+    assert!(!accepts(&odd, b""));
+    assert!(!accepts(&odd, b"aa"));
+    assert!(!accepts(&odd, b"b"));
+}
+
+#[test]
+fn complement_bytes() {
+    let foo = dfa("(?s-u:.)*foo(?s-u:.)*");
+    let no_foo = foo.complement_bytes();
+
+    for word in [&b""[..], b"fo", b"ofo", b"f\xFFoo", b"fofo"] {
+        assert!(accepts(&no_foo, word));
+    }
+    for word in [&b"foo"[..], b"xfoox", b"fofoo"] {
+        assert!(!accepts(&no_foo, word));
+    }
+
+    let roundtrip = dfa(&dfa_to_hir(no_foo).to_string());
+    assert!(accepts(&roundtrip, b"ffofo"));
+    assert!(!accepts(&roundtrip, b"ffoo"));
+}
+
+#[test]
+fn empty_language_to_hir() {
+    let keywords = dfa("if|else").difference(&dfa("[a-z]+"));
+    assert_eq!(dfa_to_hir(keywords), Hir::fail());
+
+    let nothing = dfa("(?s-u:.)*").complement_bytes();
+    let hir = dfa_to_hir(nothing);
+    assert_eq!(hir, Hir::fail());
+    assert!(!accepts(&dfa(&hir.to_string()), b""));
+}
@@ -0,0 +1,94 @@
+use regex_automata::{
+    DenseDfa, determine_and_minimize_nfa, execute_dfa, hir_to_nfa, try_hir_to_nfa,
+    try_hir_to_range_nfa,
+};
+use regex_syntax::parse;
+
+mod common;
+
+use common::WHOLE_INPUT;
+
+const PATTERNS: &[&str] = &[
+    "ab?c",
+    "ab{1,3}c",
+    "(a|b)*abb",
+    "[a-z]+[0-9]",
+    r"(?m)^[a-z]+$",
+    ".",
+];
+
+const HAYSTACKS: &[&[u8]] = &[
+    b"",
+    b"abc",
+    b"ac",
+    b"abbbc",
+    b"abbbbc",
+    b"aababb",
+    b"zz9",
+    b"hello",
+    b"\xFF",
+    "é".as_bytes(),
+];
+
+#[test]
+fn same_as_execute_dfa() {
+    for pattern in PATTERNS {
+        let hir = parse(pattern).unwrap();
+        let dfa = determine_and_minimize_nfa(try_hir_to_nfa(&hir, &WHOLE_INPUT).unwrap());
+        let dense = DenseDfa::from(&dfa);
+
+        let nfa = try_hir_to_range_nfa(&hir, &WHOLE_INPUT).unwrap();
+        let range_dfa = determine_and_minimize_nfa(nfa);
+        let range_dense = DenseDfa::from(&range_dfa);
+
+        for haystack in HAYSTACKS {
+            let expected = execute_dfa(&dfa, haystack);
+            assert_eq!(dense.execute(haystack), expected, "{pattern} {haystack:?}");
+            assert_eq!(
+                range_dense.execute(haystack),
+                expected,
+                "{pattern} {haystack:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn accepts_whole_haystack() {
+    let dfa = determine_and_minimize_nfa(hir_to_nfa(&parse("ab+").unwrap()));
+    let dense = DenseDfa::from(&dfa);
+
+    assert!(dense.accepts(b"ab"));
+    assert!(dense.accepts(b"abbb"));
+
+    assert!(!dense.accepts(b"a"));
+    assert!(!dense.accepts(b"abc"));
+    assert!(!dense.accepts(b"ba"));
+}
+
+#[test]
+fn byte_classes() {
+    let dfa = determine_and_minimize_nfa(hir_to_nfa(&parse("[a-z]+[0-9]").unwrap()));
+    let dense = DenseDfa::from(&dfa);
+
+    // [0-9], [a-z] and the other bytes
+    assert_eq!(dense.class_count(), 3);
+
+    let state = dense.next_state(dense.initial_state(), b'!');
+    assert!(dense.is_dead(state));
+    assert!(!dense.is_accept(state));
+}
+
+#[test]
+fn completed_sink_is_dead() {
+    let mut dfa = determine_and_minimize_nfa(hir_to_nfa(&parse("ab").unwrap()));
+    dfa.complete_bytes();
+    let dense = DenseDfa::from(&dfa);
+
+    let state = dense.next_state(dense.initial_state(), b'b');
+    assert!(dense.is_dead(state));
+    assert!(dense.is_dead(dense.next_state(state, b'a')));
+
+    assert_eq!(dense.execute(b"abab"), Some(2));
+    assert!(!dense.accepts(b"ba"));
+}
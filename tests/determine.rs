@@ -0,0 +1,34 @@
+use regex_automata::{MaybeSymbol, determinize, execute_dfa, hir_to_nfa};
+use regex_syntax::parse;
+
+#[test]
+fn nfa_states_of_dfa_states() {
+    let nfa = hir_to_nfa(&parse("ab|ac").unwrap());
+    let determinized = determinize(&nfa);
+    let dfa = &determinized.dfa;
+
+    assert_eq!(execute_dfa(dfa, b"ab"), Some(2));
+    assert_eq!(execute_dfa(dfa, b"ac"), Some(2));
+    assert_eq!(determinized.nfa_states.len(), dfa.states_set().len());
+
+    for (dfa_state, nfa_states) in &determinized.nfa_states {
+        assert!(nfa_states.is_sorted());
+        assert_eq!(
+            dfa.accept_states.contains(dfa_state),
+            nfa_states.iter().any(|s| nfa.accept_states.contains(s)),
+        );
+    }
+
+    let initial = &determinized.nfa_states[&dfa.initial_state()];
+    assert!(nfa.initial_states.iter().all(|s| initial.contains(s)));
+
+    // After reading `a`, the DFA is in both branches of the alternation
+    let after_a = dfa.links_from(dfa.initial_state()).next().unwrap().to;
+    let branches = nfa
+        .links()
+        .filter(|link| matches!(link.symbol, MaybeSymbol::Symbol(b'a')))
+        .map(|link| link.to);
+    for branch in branches {
+        assert!(determinized.nfa_states[&after_a].contains(&branch));
+    }
+}
@@ -0,0 +1,111 @@
+use regex_automata::{
+    BuildError, Config, MatchKind, Searcher, UnsupportedSyntax, try_hir_to_nfa,
+    try_hir_to_range_nfa, try_regex_to_nfa,
+};
+use regex_syntax::{hir::Look, parse};
+
+#[test]
+fn unsupported_look() {
+    let err = try_hir_to_nfa(&parse(r"a\b").unwrap(), &Config::default()).unwrap_err();
+    assert_eq!(
+        err,
+        BuildError::Unsupported {
+            syntax: UnsupportedSyntax::UnicodeWordBoundary(Look::WordUnicode),
+            span: None,
+        }
+    );
+}
+
+#[test]
+fn unsupported_look_span() {
+    let err = try_regex_to_nfa(r"(?-u:\b)a(?-u)\b(?u)\b", &Config::default()).unwrap_err();
+    let BuildError::Unsupported {
+        span: Some(span), ..
+    } = err
+    else {
+        panic!("unexpected error {err:?}");
+    };
+    assert_eq!(span.start.offset, 20);
+    assert_eq!(span.end.offset, 22);
+}
+
+#[test]
+fn look_at_match_end() {
+    let config = Config::default();
+    for pattern in [r"a$", r"(?-u)a\b", r"(?m)a$", r"(?-u)a\B", r"a(?:$|b)"] {
+        assert_eq!(
+            try_regex_to_nfa(pattern, &config).unwrap_err(),
+            BuildError::Unsupported {
+                syntax: UnsupportedSyntax::LookAtMatchEnd,
+                span: None,
+            },
+            "{pattern}"
+        );
+    }
+
+    // Assertions checked before the end of the match
+    for pattern in [r"^a", r"(?m)a$\nb", r"(?-u)\ba\b-", r"\Aab"] {
+        assert!(try_regex_to_nfa(pattern, &config).is_ok(), "{pattern}");
+    }
+
+    let config = Config {
+        whole_input: true,
+        ..Default::default()
+    };
+    for pattern in [r"a$", r"(?-u)a\b", r"a(?:$|b)"] {
+        assert!(try_regex_to_nfa(pattern, &config).is_ok(), "{pattern}");
+    }
+}
+
+#[test]
+fn syntax_error() {
+    let err = try_regex_to_nfa(r"a(b", &Config::default()).unwrap_err();
+    assert!(matches!(err, BuildError::Syntax(_)));
+}
+
+#[test]
+fn size_limit() {
+    let config = Config {
+        size_limit: Some(20),
+        ..Default::default()
+    };
+    assert!(try_regex_to_nfa("abc", &config).is_ok());
+    assert_eq!(
+        try_regex_to_nfa("a{100}", &config).unwrap_err(),
+        BuildError::SizeLimitExceeded { limit: 20 }
+    );
+    assert_eq!(
+        try_regex_to_nfa("(?m)^[a\n]{3}$", &config).unwrap_err(),
+        BuildError::SizeLimitExceeded { limit: 20 }
+    );
+}
+
+#[test]
+fn size_limit_is_never_exceeded() {
+    for pattern in [
+        r"(?m)^[a-z\n]{2}$",
+        r"(?-u:\b)[a-z]+(?-u:\b)",
+        "[a-c]x|[b-d]y",
+    ] {
+        let hir = parse(pattern).unwrap();
+        for limit in 1..200 {
+            let config = Config {
+                size_limit: Some(limit),
+                whole_input: true,
+            };
+            let exceeded = Err(BuildError::SizeLimitExceeded { limit });
+
+            match try_hir_to_nfa(&hir, &config) {
+                Ok(nfa) => assert!(nfa.link_count() <= limit, "{pattern} {limit}"),
+                err => assert_eq!(err.map(|_| ()), exceeded, "{pattern} {limit}"),
+            }
+            match try_hir_to_range_nfa(&hir, &config) {
+                Ok(nfa) => assert!(nfa.link_count() <= limit, "{pattern} {limit}"),
+                err => assert_eq!(err.map(|_| ()), exceeded, "{pattern} {limit}"),
+            }
+            if let Err(err) = Searcher::try_new(&hir, &config, MatchKind::LeftmostLongest) {
+                assert_eq!(Err(err), exceeded, "{pattern} {limit}");
+            }
+        }
+    }
+}
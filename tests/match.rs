@@ -1,5 +1,19 @@
-use regex_automata::{determine_and_minimize_nfa, execute_dfa, hir_to_nfa};
-use regex_syntax::parse;
+use regex_automata::{
+    determine_and_minimize_nfa, execute_dfa, hir_to_nfa, hir_to_range_nfa, try_hir_to_nfa,
+};
+use regex_syntax::{ParserBuilder, hir::Hir, parse};
+
+mod common;
+
+use common::WHOLE_INPUT;
+
+fn parse_bytes(pattern: &str) -> Hir {
+    ParserBuilder::new()
+        .utf8(false)
+        .build()
+        .parse(pattern)
+        .unwrap()
+}
 
 #[test]
 fn optional() {
@@ -31,3 +45,111 @@ fn repeat_bounded() {
     assert!(execute_dfa(&dfa, b"ac").is_none());
     assert!(execute_dfa(&dfa, b"abbbbc").is_none());
 }
+
+#[test]
+fn anchors() {
+    let nfa = try_hir_to_nfa(&parse(r"^ab\z").unwrap(), &WHOLE_INPUT).unwrap();
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"ab"), Some(2));
+
+    assert!(execute_dfa(&dfa, b"a").is_none());
+    assert!(execute_dfa(&dfa, b"_ab").is_none());
+
+    let nfa = hir_to_nfa(&parse(r"a^b").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert!(execute_dfa(&dfa, b"ab").is_none());
+}
+
+#[test]
+fn multi_line_anchors() {
+    let nfa = hir_to_nfa(&parse(r"(?m)a$\n^b").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"a\nb"), Some(3));
+
+    let nfa = hir_to_nfa(&parse(r"(?m)a$b").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert!(execute_dfa(&dfa, b"ab").is_none());
+
+    let nfa = hir_to_nfa(&parse(r"(?Rm)a$\r\n^b").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"a\r\nb"), Some(4));
+}
+
+#[test]
+fn word_boundary() {
+    let nfa = try_hir_to_nfa(&parse(r"(?-u)\bab\b").unwrap(), &WHOLE_INPUT).unwrap();
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"ab"), Some(2));
+
+    let nfa = hir_to_nfa(&parse(r"(?-u)[a ]\bb").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b" b"), Some(2));
+    assert!(execute_dfa(&dfa, b"ab").is_none());
+
+    let nfa = hir_to_nfa(&parse(r"(?-u)a\Bb").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"ab"), Some(2));
+}
+
+#[test]
+fn unicode_class() {
+    let nfa = hir_to_nfa(&parse(".").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b"a"), Some(1));
+    assert_eq!(execute_dfa(&dfa, "é".as_bytes()), Some(2));
+    assert_eq!(execute_dfa(&dfa, "日".as_bytes()), Some(3));
+    assert_eq!(execute_dfa(&dfa, "🦀".as_bytes()), Some(4));
+
+    assert!(execute_dfa(&dfa, b"\n").is_none());
+    assert!(execute_dfa(&dfa, b"\xFF").is_none());
+    assert!(execute_dfa(&dfa, b"\xE6\x97").is_none());
+
+    let nfa = hir_to_nfa(&parse("[α-γ日]+z").unwrap());
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, "αγ日z".as_bytes()), Some(8));
+    assert!(execute_dfa(&dfa, "δz".as_bytes()).is_none());
+}
+
+#[test]
+fn byte_ranges() {
+    let nfa = hir_to_range_nfa(&parse_bytes(r"(?-u)[a-z]+[0-9]|[m-p]x|[\x00-\xFF]"));
+    let mut dfa = determine_and_minimize_nfa(nfa);
+    dfa.merge_ranges();
+
+    assert_eq!(execute_dfa(&dfa, b"\xFF"), Some(1));
+    assert_eq!(execute_dfa(&dfa, b"abc1"), Some(1));
+
+    let nfa = hir_to_range_nfa(&parse_bytes(r"(?-u)[a-z]+[0-9]|[m-p]x"));
+    let mut dfa = determine_and_minimize_nfa(nfa);
+    dfa.merge_ranges();
+
+    assert_eq!(dfa.link_count(), 11);
+    assert_eq!(execute_dfa(&dfa, b"abc1"), Some(4));
+    assert_eq!(execute_dfa(&dfa, b"nx"), Some(2));
+    assert_eq!(execute_dfa(&dfa, b"nn7"), Some(3));
+
+    assert!(execute_dfa(&dfa, b"ax").is_none());
+    assert!(execute_dfa(&dfa, b"A1").is_none());
+}
+
+#[test]
+fn byte_ranges_word_boundary() {
+    let nfa = hir_to_range_nfa(&parse_bytes(r"(?s-u).\b."));
+    let dfa = determine_and_minimize_nfa(nfa);
+
+    assert_eq!(execute_dfa(&dfa, b" a"), Some(2));
+    assert_eq!(execute_dfa(&dfa, b"_\n"), Some(2));
+
+    assert!(execute_dfa(&dfa, b"ab").is_none());
+    assert!(execute_dfa(&dfa, b"\n ").is_none());
+}
@@ -0,0 +1,79 @@
+use regex_automata::{
+    Config, DenseDfa, Dfa, Match, MatchKind, Searcher, determine_and_minimize_nfa,
+    determine_nfa_leftmost_first, execute_dfa_with, hir_to_nfa, minimize_hopcroft, try_hir_to_nfa,
+};
+use regex_syntax::parse;
+
+fn execute(pattern: &str, haystack: &[u8], kind: MatchKind) -> Option<usize> {
+    let dfa = determine_and_minimize_nfa(hir_to_nfa(&parse(pattern).unwrap()));
+    execute_with(&dfa, haystack, kind)
+}
+
+/// Leftmost-first match of `pattern`
+fn execute_first(pattern: &str, haystack: &[u8]) -> Option<usize> {
+    let dfa = determine_nfa_leftmost_first(&hir_to_nfa(&parse(pattern).unwrap()));
+    execute_with(&dfa, haystack, MatchKind::LeftmostLongest)
+}
+
+fn execute_with(dfa: &Dfa<u8>, haystack: &[u8], kind: MatchKind) -> Option<usize> {
+    let result = execute_dfa_with(dfa, haystack, kind);
+    assert_eq!(DenseDfa::from(dfa).execute_with(haystack, kind), result);
+    result
+}
+
+#[test]
+fn earliest() {
+    assert_eq!(execute("a+", b"aaa", MatchKind::Earliest), Some(1));
+    assert_eq!(execute("a*", b"aaa", MatchKind::Earliest), Some(0));
+    assert_eq!(execute("b", b"aaa", MatchKind::Earliest), None);
+}
+
+#[test]
+fn leftmost_longest() {
+    assert_eq!(execute("a+", b"aaa", MatchKind::LeftmostLongest), Some(3));
+    assert_eq!(execute("a+?", b"aaa", MatchKind::LeftmostLongest), Some(3));
+    assert_eq!(execute("a|ab", b"abc", MatchKind::LeftmostLongest), Some(2));
+    assert_eq!(
+        execute("(?s:.)*?b", b"abab", MatchKind::LeftmostLongest),
+        Some(4)
+    );
+    assert_eq!(execute("ab", b"aab", MatchKind::LeftmostLongest), None);
+
+    // `$` can't see the rest of the haystack from a prefix match
+    let hir = parse("a$").unwrap();
+    assert!(try_hir_to_nfa(&hir, &Config::default()).is_err());
+    let searcher = Searcher::new(&hir);
+    assert_eq!(searcher.find(b"aa"), Some(Match { start: 1, end: 2 }));
+}
+
+#[test]
+fn leftmost_first() {
+    assert_eq!(execute_first("a+", b"aaa"), Some(3));
+    assert_eq!(execute_first("a+?", b"aaa"), Some(1));
+    assert_eq!(execute_first("a*?", b"aaa"), Some(0));
+    assert_eq!(execute_first("a{2,4}?", b"aaaa"), Some(2));
+    assert_eq!(execute_first("(?U)a+", b"aaa"), Some(1));
+    assert_eq!(execute_first("a|ab", b"abc"), Some(1));
+    assert_eq!(execute_first("ab|a", b"abc"), Some(2));
+    assert_eq!(execute_first("(?s:.)*?b", b"abab"), Some(2));
+    assert_eq!(execute_first("(?:a|ab)(?:c|bcd)", b"abcd"), Some(4));
+    assert_eq!(execute_first("ab", b"aab"), None);
+}
+
+#[test]
+fn leftmost_first_minimized() {
+    let nfa = hir_to_nfa(&parse("(?:a|ab)(?:b|c)*?").unwrap());
+    let dfa = determine_nfa_leftmost_first(&nfa);
+    let minimal = minimize_hopcroft(dfa.clone());
+
+    for haystack in [&b"a"[..], b"ab", b"abb", b"abc", b"ac", b"b"] {
+        assert_eq!(
+            execute_dfa_with(&minimal, haystack, MatchKind::LeftmostLongest),
+            execute_dfa_with(&dfa, haystack, MatchKind::LeftmostLongest),
+        );
+    }
+    assert_eq!(
+        execute_dfa_with(&minimal, b"abcb", MatchKind::LeftmostLongest),
+        Some(1)
+    );
+}
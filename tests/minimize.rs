@@ -0,0 +1,69 @@
+use regex_automata::{
+    Minimization, determine_and_minimize_nfa, determine_and_minimize_nfa_with, determine_nfa,
+    execute_dfa, hir_to_nfa, minimize_hopcroft,
+};
+use regex_syntax::parse;
+
+mod common;
+
+use common::nfa;
+
+const PATTERNS: &[&str] = &[
+    "a",
+    "abc",
+    "a|b|c",
+    "(a|b)*",
+    "(a|b)*abb(a|b)*",
+    "(a|b)*a(a|b){3}",
+    "ab{1,3}c",
+    "(?m)^a$",
+    "(ab|ac|ad)*e?",
+];
+
+#[test]
+fn hopcroft_same_size_as_brzozowski() {
+    for pattern in PATTERNS {
+        let brzozowski = determine_and_minimize_nfa(nfa(pattern));
+        let hopcroft = determine_and_minimize_nfa_with(nfa(pattern), Minimization::Hopcroft);
+
+        assert_eq!(
+            brzozowski.states_set().len(),
+            hopcroft.states_set().len(),
+            "{pattern}"
+        );
+        assert_eq!(brzozowski.link_count(), hopcroft.link_count(), "{pattern}");
+    }
+}
+
+#[test]
+fn hopcroft_reduces_subset_construction() {
+    let nfa = hir_to_nfa(&parse("(a|b)*a(a|b){3}").unwrap());
+    let dfa = determine_nfa(&nfa);
+    let states = dfa.states_set().len();
+
+    let minimal = minimize_hopcroft(dfa);
+    assert_eq!(minimal.states_set().len(), 16);
+    assert!(minimal.states_set().len() < states);
+
+    assert_eq!(execute_dfa(&minimal, b"bbabab"), Some(6));
+    assert_eq!(execute_dfa(&minimal, b"aaaa"), Some(4));
+    assert!(execute_dfa(&minimal, b"bbbbbb").is_none());
+}
+
+#[test]
+fn hopcroft_empty_language() {
+    let nfa = hir_to_nfa(&parse(r"a\Ab").unwrap());
+    let dfa = determine_and_minimize_nfa_with(nfa, Minimization::Hopcroft);
+
+    assert!(dfa.accept_states.is_empty());
+    assert_eq!(dfa.link_count(), 0);
+}
+
+#[test]
+fn hopcroft_large_dfa() {
+    let nfa = hir_to_nfa(&parse("(a|b)*a(a|b){12}").unwrap());
+    let minimal = minimize_hopcroft(determine_nfa(&nfa));
+
+    assert_eq!(minimal.states_set().len(), 1 << 13);
+    assert_eq!(minimal.link_count(), 1 << 14);
+}
@@ -0,0 +1,112 @@
+use regex_automata::dfa_to_hir;
+
+mod common;
+
+use common::{accepts, dfa, range_dfa};
+
+#[test]
+fn intersect() {
+    let allowed = dfa("[a-z0-9]{2,4}");
+    let format = dfa("[a-z]+[0-9]*");
+    let both = allowed.intersect(&format);
+
+    for word in [&b"ab"[..], b"ab12", b"abcd", b"a1"] {
+        assert!(accepts(&both, word));
+    }
+    for word in [&b"a"[..], b"12", b"1a", b"abc12", b""] {
+        assert!(!accepts(&both, word));
+    }
+
+    let hir = dfa_to_hir(both);
+    let roundtrip = dfa(&hir.to_string());
+    assert!(accepts(&roundtrip, b"abc1"));
+    assert!(!accepts(&roundtrip, b"abcde"));
+}
+
+#[test]
+fn intersect_ranges() {
+    // Ranges split differently on each side
+    let letters = range_dfa("[a-z]+");
+    let mixed = range_dfa("[m-p0-9]+");
+    let both = letters.intersect(&mixed);
+
+    for word in [&b"m"[..], b"mop", b"ponm"] {
+        assert!(accepts(&both, word));
+    }
+    for word in [&b"a"[..], b"mz", b"m0", b""] {
+        assert!(!accepts(&both, word));
+    }
+
+    let difference = letters.difference(&mixed);
+    assert!(accepts(&difference, b"ma"));
+    assert!(!accepts(&difference, b"mo"));
+}
+
+#[test]
+fn intersect_reachable_only() {
+    let even = dfa("(?:aa)*");
+    let odd = dfa("a(?:aa)*");
+
+    let none = even.intersect(&odd);
+    assert!(none.accept_states.is_empty());
+    assert_eq!(none.states_set().len(), 2);
+
+    let same = even.intersect(&even);
+    assert_eq!(same.states_set().len(), 2);
+    assert!(accepts(&same, b"aaaa"));
+}
+
+#[test]
+fn union() {
+    let digits = dfa("[0-9]+");
+    let words = dfa("[a-z]+");
+    let either = digits.union(&words);
+
+    assert!(accepts(&either, b"123"));
+    assert!(accepts(&either, b"abc"));
+    assert!(!accepts(&either, b"a1"));
+    assert!(!accepts(&either, b""));
+
+    // Minimal: initial state, digits and letters
+    assert_eq!(either.states_set().len(), 3);
+
+    let roundtrip = dfa(&dfa_to_hir(either).to_string());
+    assert!(accepts(&roundtrip, b"42"));
+    assert!(!accepts(&roundtrip, b"4a"));
+}
+
+#[test]
+fn difference() {
+    let names = dfa("[a-z]+");
+    let reserved = dfa("if|else");
+    let allowed = names.difference(&reserved);
+
+    for word in [&b"i"[..], b"iff", b"els", b"elsee", b"x"] {
+        assert!(accepts(&allowed, word));
+    }
+    for word in [&b"if"[..], b"else", b"", b"A"] {
+        assert!(!accepts(&allowed, word));
+    }
+
+    let hir = dfa_to_hir(allowed);
+    let roundtrip = dfa(&hir.to_string());
+    assert!(accepts(&roundtrip, b"ifs"));
+    assert!(!accepts(&roundtrip, b"if"));
+
+    assert!(reserved.difference(&names).accept_states.is_empty());
+}
+
+#[test]
+fn symmetric_difference() {
+    let left = dfa("a{1,3}");
+    let right = dfa("a{2,4}");
+    let either = left.symmetric_difference(&right);
+
+    assert!(accepts(&either, b"a"));
+    assert!(accepts(&either, b"aaaa"));
+    assert!(!accepts(&either, b"aa"));
+    assert!(!accepts(&either, b"aaa"));
+    assert!(!accepts(&either, b"aaaaa"));
+
+    assert!(left.symmetric_difference(&left).accept_states.is_empty());
+}
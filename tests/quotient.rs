@@ -0,0 +1,78 @@
+use regex_automata::equivalent;
+
+mod common;
+
+use common::dfa;
+
+#[test]
+fn prefixes_suffixes_factors() {
+    let dfa = dfa("abc|de");
+    assert_eq!(
+        equivalent(&dfa.prefixes(), &self::dfa("|a|ab|abc|d|de")),
+        Ok(())
+    );
+    assert_eq!(
+        equivalent(&dfa.suffixes(), &self::dfa("|c|bc|abc|e|de")),
+        Ok(())
+    );
+    assert_eq!(
+        equivalent(&dfa.factors(), &self::dfa("|a|b|c|ab|bc|abc|d|e|de")),
+        Ok(())
+    );
+
+    let empty = self::dfa("[a&&b]");
+    assert!(empty.prefixes().is_empty());
+    assert!(empty.suffixes().is_empty());
+    assert!(empty.factors().is_empty());
+}
+
+#[test]
+fn quotients_by_word() {
+    let dfa = dfa("ab+c|ad");
+    assert_eq!(
+        equivalent(&dfa.left_quotient(b"a"), &self::dfa("b+c|d")),
+        Ok(())
+    );
+    assert_eq!(equivalent(&dfa.left_quotient(b""), &dfa), Ok(()));
+    assert!(dfa.left_quotient(b"ax").is_empty());
+
+    assert_eq!(
+        equivalent(&dfa.right_quotient(b"c"), &self::dfa("ab+")),
+        Ok(())
+    );
+    assert_eq!(
+        equivalent(&dfa.right_quotient(b"bc"), &self::dfa("ab*")),
+        Ok(())
+    );
+    assert!(dfa.right_quotient(b"x").is_empty());
+}
+
+#[test]
+fn quotients_by_language() {
+    let dfa = dfa("[0-9]+(px|em)");
+    assert_eq!(
+        equivalent(
+            &dfa.left_quotient_by(&self::dfa("1|22")),
+            &self::dfa("[0-9]*(px|em)")
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        equivalent(
+            &dfa.right_quotient_by(&self::dfa("px|m")),
+            &self::dfa("[0-9]+(|e)")
+        ),
+        Ok(())
+    );
+    assert!(dfa.left_quotient_by(&self::dfa("px")).is_empty());
+}
+
+#[test]
+fn partial_input() {
+    let dfa = dfa("let [a-z]+ = [0-9]+;");
+    let can_complete = |input: &[u8]| !dfa.left_quotient(input).is_empty();
+
+    assert!(can_complete(b"let x = 4"));
+    assert!(can_complete(b"let x = 42;"));
+    assert!(!can_complete(b"let x = x"));
+}
@@ -1,14 +1,30 @@
 use expect_test::expect;
-use regex_automata::{determine_and_minimize_nfa, dfa_to_hir, hir_to_nfa};
-use regex_syntax::parse;
+use regex_automata::{
+    determine_and_minimize_nfa, dfa_to_hir, equivalent, hir_to_nfa, try_hir_to_nfa,
+};
+use regex_syntax::{ParserBuilder, parse};
+
+mod common;
+
+use common::WHOLE_INPUT;
 
 fn roundtrip(pattern: &str) -> String {
     let hir_in = parse(pattern).unwrap();
-    let nfa = hir_to_nfa(&hir_in);
+    let nfa = try_hir_to_nfa(&hir_in, &WHOLE_INPUT).unwrap();
     //std::fs::write("nfa.dot", nfa.to_dot()).unwrap();
     let dfa = determine_and_minimize_nfa(nfa);
     //std::fs::write("dfa.dot", dfa.to_dot()).unwrap();
-    let hir_out = dfa_to_hir(dfa);
+    let hir_out = dfa_to_hir(dfa.clone());
+
+    // The output REGEX has to mean the same as the input one
+    let hir_back = ParserBuilder::new()
+        .utf8(false)
+        .build()
+        .parse(&hir_out.to_string())
+        .unwrap();
+    let dfa_back = determine_and_minimize_nfa(hir_to_nfa(&hir_back));
+    assert_eq!(equivalent(&dfa, &dfa_back), Ok(()));
+
     hir_out.to_string()
 }
 
@@ -26,3 +42,23 @@ expect_test!(word, "abc" => expect!["(?:abc)"]);
 expect_test!(letter_choice, "a|b|c" => expect!["[a-c]"]);
 expect_test!(bug1, r#"(a|b)*"# => expect!["[ab]*"]);
 expect_test!(bug2, r#"(a|b)*abb(a|b)*"# => expect!["(?:b*aa*b(?:aa*b)*b[ab]*)"]);
+expect_test!(anchors, r#"^abc$"# => expect!["(?:abc)"]);
+expect_test!(word_boundary, r#"(?-u:\b)a(?-u:\b)"# => expect!["a"]);
+expect_test!(unicode_class, r#"[αβ]"# => expect![[r#"(?:(?-u:\xCE)(?-u:[\xB1\xB2]))"#]]);
+
+#[test]
+fn large_dfa() {
+    const LEN: u32 = 5_000;
+
+    let mut dfa = regex_automata::Dfa::new();
+    dfa.initial_states.insert(0);
+    dfa.accept_states.insert(LEN);
+    for state in 0..LEN {
+        dfa.link(state, state + 1, b'a');
+        dfa.link(state, state + 1, b'b');
+    }
+
+    let hir = dfa_to_hir(dfa);
+    assert_eq!(hir.properties().minimum_len(), Some(LEN as usize));
+    assert_eq!(hir.properties().maximum_len(), Some(LEN as usize));
+}
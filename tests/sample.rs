@@ -0,0 +1,59 @@
+use rand::{SeedableRng, rngs::SmallRng};
+use rustc_hash::FxHashMap;
+
+mod common;
+
+use common::{accepts, dfa};
+
+#[test]
+fn sample_accepted_words() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let dfa = dfa("[a-z]{2}-[0-9]+|[A-Z]{8}");
+
+    for _ in 0..100 {
+        let word = dfa.sample(8, &mut rng).unwrap();
+        assert_eq!(word.len(), 8);
+        assert!(accepts(&dfa, &word));
+    }
+    assert_eq!(dfa.sample(3, &mut rng), None);
+    assert_eq!(dfa.sample(0, &mut rng), None);
+}
+
+#[test]
+fn sample_uniformly() {
+    let mut rng = SmallRng::seed_from_u64(1);
+    // 1 word starting with `a`, 9 with `b`
+    let dfa = dfa("aa|b[a-i]");
+
+    let mut counts: FxHashMap<Vec<u8>, usize> = Default::default();
+    for _ in 0..10_000 {
+        *counts.entry(dfa.sample(2, &mut rng).unwrap()).or_default() += 1;
+    }
+
+    assert_eq!(counts.len(), 10);
+    for count in counts.values() {
+        assert!((800..1200).contains(count), "{counts:?}");
+    }
+}
+
+#[test]
+fn sample_weighted() {
+    let mut rng = SmallRng::seed_from_u64(2);
+    let dfa = dfa("[ab]*c");
+
+    let mut lengths = Vec::new();
+    for _ in 0..1000 {
+        let word = dfa.sample_weighted(0.25, &mut rng).unwrap();
+        assert!(accepts(&dfa, &word));
+        lengths.push(word.len());
+    }
+    // Half of the weight is on the word `c`
+    let shortest = lengths.iter().filter(|len| **len == 1).count();
+    assert!((400..600).contains(&shortest), "{shortest}");
+    assert!(lengths.iter().any(|len| *len > 3));
+
+    // Too heavy for the weights to sum up
+    assert_eq!(dfa.sample_weighted(0.5, &mut rng), None);
+    assert_eq!(self::dfa("[a&&b]").sample_weighted(0.1, &mut rng), None);
+    assert_eq!(self::dfa("a?").sample_weighted(0.0, &mut rng), Some(vec![]));
+}
@@ -0,0 +1,119 @@
+use regex_automata::{Config, Match, MatchKind, Searcher};
+use regex_syntax::parse;
+
+fn spans(pattern: &str, haystack: &[u8]) -> Vec<(usize, usize)> {
+    spans_of(&Searcher::new(&parse(pattern).unwrap()), haystack)
+}
+
+fn spans_of(searcher: &Searcher, haystack: &[u8]) -> Vec<(usize, usize)> {
+    searcher
+        .find_iter(haystack)
+        .map(|span| (span.start, span.end))
+        .collect()
+}
+
+fn earliest_searcher(pattern: &str) -> Searcher {
+    let hir = parse(pattern).unwrap();
+    Searcher::try_new(&hir, &Config::default(), MatchKind::Earliest).unwrap()
+}
+
+fn leftmost_first_searcher(pattern: &str) -> Searcher {
+    Searcher::try_new_leftmost_first(&parse(pattern).unwrap(), &Config::default()).unwrap()
+}
+
+#[test]
+fn find_in_the_middle() {
+    let searcher = Searcher::new(&parse("[0-9]+").unwrap());
+
+    assert_eq!(
+        searcher.find(b"abc 123 def"),
+        Some(Match { start: 4, end: 7 })
+    );
+    assert_eq!(searcher.find(b"42"), Some(Match { start: 0, end: 2 }));
+    assert_eq!(searcher.find(b"abc"), None);
+    assert_eq!(searcher.find(b""), None);
+}
+
+#[test]
+fn leftmost_longest() {
+    assert_eq!(spans("a+b", b"xaaab aab"), vec![(1, 5), (6, 9)]);
+    assert_eq!(spans("a+", b"aaa"), vec![(0, 3)]);
+    assert_eq!(spans("abc|b", b"abc"), vec![(0, 3)]);
+    assert_eq!(spans("abcd|c", b"abcd"), vec![(0, 4)]);
+    assert_eq!(spans("a|ab", b"abab"), vec![(0, 2), (2, 4)]);
+    assert_eq!(spans("a*", b"baa"), vec![(0, 0), (1, 3)]);
+}
+
+#[test]
+fn leftmost_first() {
+    assert_eq!(
+        spans_of(&leftmost_first_searcher("a|ab"), b"abab"),
+        vec![(0, 1), (2, 3)]
+    );
+    assert_eq!(
+        spans_of(&leftmost_first_searcher("a+?"), b"aaa"),
+        vec![(0, 1), (1, 2), (2, 3)]
+    );
+    assert_eq!(
+        spans_of(&leftmost_first_searcher("abcd|c"), b"abcd"),
+        vec![(0, 4)]
+    );
+    assert_eq!(
+        spans_of(&leftmost_first_searcher("b|abc"), b"abc"),
+        vec![(0, 3)]
+    );
+    assert_eq!(
+        spans_of(&leftmost_first_searcher(r"a$|a"), b"aa"),
+        vec![(0, 1), (1, 2)]
+    );
+}
+
+#[test]
+fn earliest_end() {
+    assert_eq!(
+        spans_of(&earliest_searcher("[0-9]+"), b"abc 123"),
+        vec![(4, 5), (5, 6), (6, 7)]
+    );
+    assert_eq!(spans_of(&earliest_searcher("abc|b"), b"abc"), vec![(1, 2)]);
+}
+
+#[test]
+fn find_iter() {
+    assert_eq!(spans("ab", b"ab_abab"), vec![(0, 2), (3, 5), (5, 7)]);
+    assert_eq!(spans("x", b"abc"), vec![]);
+}
+
+#[test]
+fn empty_matches() {
+    assert_eq!(spans("", b"ab"), vec![(0, 0), (1, 1), (2, 2)]);
+    assert_eq!(spans("a*", b""), vec![(0, 0)]);
+}
+
+#[test]
+fn anchors() {
+    assert_eq!(spans("^a", b"aaa"), vec![(0, 1)]);
+    assert_eq!(spans("a$", b"aaa"), vec![(2, 3)]);
+    assert_eq!(spans("(?m)^a", b"ab\nab\n"), vec![(0, 1), (3, 4)]);
+    assert_eq!(spans("(?m)b$", b"ab\nab"), vec![(1, 2), (4, 5)]);
+}
+
+#[test]
+fn word_boundaries() {
+    assert_eq!(
+        spans(r"(?-u:\b)ab(?-u:\b)", b"ab cab ab_ ab"),
+        vec![(0, 2), (11, 13)]
+    );
+    assert_eq!(spans(r"(?-u:\B)b", b"ab b"), vec![(1, 2)]);
+}
+
+#[test]
+fn find_at_sees_previous_bytes() {
+    let searcher = Searcher::new(&parse(r"(?-u:\b)b").unwrap());
+
+    assert_eq!(
+        searcher.find_at(b"ab b", 1),
+        Some(Match { start: 3, end: 4 })
+    );
+    assert_eq!(searcher.find_at(b"ab b", 4), None);
+    assert_eq!(searcher.find_at(b"ab b", 5), None);
+}
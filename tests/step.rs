@@ -0,0 +1,56 @@
+use regex_automata::{ByteRange, Dfa, StepResult, determine_and_minimize_nfa, hir_to_range_nfa};
+use regex_syntax::parse;
+
+mod common;
+
+use common::dfa;
+
+#[test]
+fn step() {
+    let dfa = dfa("[0-9]{3}-[0-9]{4}");
+    let mut stepper = dfa.stepper();
+
+    assert_eq!(stepper.result(), StepResult::Live);
+    assert_eq!(stepper.step_all(b"555-01"), StepResult::Live);
+    assert_eq!(stepper.step(&b'2'), StepResult::Live);
+    assert_eq!(stepper.step(&b'3'), StepResult::Accepted);
+    assert_eq!(stepper.step(&b'4'), StepResult::Dead);
+    assert_eq!(stepper.step(&b'5'), StepResult::Dead);
+    assert_eq!(stepper.state(), None);
+
+    stepper.reset();
+    assert_eq!(stepper.step_all(b"55a"), StepResult::Dead);
+    stepper.reset();
+    assert_eq!(stepper.step_all(b"555-0123"), StepResult::Accepted);
+}
+
+#[test]
+fn dead_states_are_detected_early() {
+    // The DFA can read on after "ax", but never reaches an accept state
+    let mut dfa = dfa("abc");
+    let after_a = dfa.links_from(dfa.initial_state()).next().unwrap().to;
+    let stuck = dfa.next_counter().next();
+    dfa.link(after_a, stuck, b'x');
+    dfa.link(stuck, stuck, b'x');
+
+    let mut stepper = dfa.stepper();
+    assert_eq!(stepper.step_all(b"a"), StepResult::Live);
+    assert_eq!(stepper.step(&b'x'), StepResult::Dead);
+}
+
+#[test]
+fn empty_language() {
+    let dfa = dfa("[a&&b]");
+    assert_eq!(dfa.stepper().result(), StepResult::Dead);
+    assert_eq!(self::dfa("a*").stepper().result(), StepResult::Accepted);
+}
+
+#[test]
+fn byte_ranges() {
+    let nfa = hir_to_range_nfa(&parse("[a-f]+!").unwrap());
+    let dfa: Dfa<ByteRange> = determine_and_minimize_nfa(nfa);
+
+    let mut stepper = dfa.stepper();
+    assert_eq!(stepper.step_all(b"cafe"), StepResult::Live);
+    assert_eq!(stepper.step(&b'!'), StepResult::Accepted);
+}
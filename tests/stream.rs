@@ -0,0 +1,122 @@
+use std::io::{BufReader, Cursor, Read};
+
+use regex_automata::{
+    MatchKind, Matcher, determine_and_minimize_nfa, execute_buf_read, execute_dfa_with,
+    execute_read, hir_to_range_nfa,
+};
+use regex_syntax::parse;
+
+mod common;
+
+use common::dfa;
+
+/// Reader returning a single byte per read
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[test]
+fn same_as_whole_haystack() {
+    let cases: [(&str, &[u8]); 4] = [
+        ("a+b", b"aaab"),
+        ("(ab)*", b"ababa"),
+        ("foo|foobar", b"foobarbaz"),
+        ("x", b"abc"),
+    ];
+
+    for (pattern, haystack) in cases {
+        let dfa = dfa(pattern);
+        for kind in [MatchKind::Earliest, MatchKind::LeftmostLongest] {
+            let expected = execute_dfa_with(&dfa, haystack, kind);
+            for split in 0..=haystack.len() {
+                let (left, right) = haystack.split_at(split);
+                let mut matcher = Matcher::new(&dfa, kind);
+                matcher.feed(left);
+                matcher.feed(right);
+                assert_eq!(matcher.finish(), expected, "{pattern} {kind:?} {split}");
+            }
+        }
+    }
+}
+
+#[test]
+fn match_across_chunks() {
+    let dfa = dfa("hello world");
+    let mut matcher = Matcher::new(&dfa, MatchKind::LeftmostLongest);
+
+    assert_eq!(matcher.feed(b"hel"), None);
+    assert_eq!(matcher.feed(b"lo wo"), None);
+    assert_eq!(matcher.offset(), 8);
+    assert!(!matcher.is_done());
+    assert_eq!(matcher.feed(b"rld!!"), Some(Some(11)));
+    assert!(matcher.is_done());
+    assert_eq!(matcher.feed(b"more"), Some(Some(11)));
+    assert_eq!(matcher.finish(), Some(11));
+
+    let mut matcher = Matcher::new(&dfa, MatchKind::LeftmostLongest);
+    matcher.feed(b"hello world");
+    assert!(!matcher.is_done());
+    assert_eq!(matcher.finish(), Some(11));
+
+    let mut matcher = Matcher::new(&dfa, MatchKind::Earliest);
+    assert_eq!(matcher.feed(b"help"), Some(None));
+    assert!(matcher.is_done());
+    assert_eq!(matcher.finish(), None);
+}
+
+#[test]
+fn readers() {
+    let dfa = dfa("[a-z]+[0-9]*");
+    let haystack = b"abc123 rest";
+
+    let result = execute_read(&dfa, Trickle(haystack), MatchKind::LeftmostLongest);
+    assert_eq!(result.unwrap(), Some(6));
+    let result = execute_read(&dfa, Cursor::new(haystack), MatchKind::Earliest);
+    assert_eq!(result.unwrap(), Some(1));
+
+    let nfa = hir_to_range_nfa(&parse("[a-z]+[0-9]*").unwrap());
+    let ranges = determine_and_minimize_nfa(nfa);
+    let reader = BufReader::with_capacity(2, Cursor::new(haystack));
+    let result = execute_buf_read(&ranges, reader, MatchKind::LeftmostLongest);
+    assert_eq!(result.unwrap(), Some(6));
+}
+
+#[test]
+fn done_in_dead_state() {
+    let mut ab = dfa("ab");
+    ab.complete_bytes();
+
+    let mut matcher = Matcher::new(&ab, MatchKind::LeftmostLongest);
+    assert_eq!(matcher.feed(b"a"), None);
+    assert!(!matcher.is_done());
+    assert_eq!(matcher.feed(b"x"), Some(None));
+    assert!(matcher.is_done());
+    assert_eq!(matcher.finish(), None);
+
+    let mut ab_plus = dfa("(?:ab)+");
+    ab_plus.complete_bytes();
+    let live = ab_plus.coreachable_states();
+    for haystack in [&b"ababa"[..], b"ab", b"abab"] {
+        let mut matcher = Matcher::with_live_states(&ab_plus, MatchKind::LeftmostLongest, &live);
+        assert_eq!(matcher.feed(haystack), None);
+        assert_eq!(matcher.feed(b"c"), Some(Some(haystack.len() / 2 * 2)));
+        assert!(matcher.is_done());
+    }
+
+    // Endless inputs
+    let endless = || Cursor::new(b"ab").chain(std::io::repeat(b'x'));
+    for kind in [MatchKind::Earliest, MatchKind::LeftmostLongest] {
+        assert_eq!(execute_read(&ab, endless(), kind).unwrap(), Some(2));
+        let result = execute_read(&ab, std::io::repeat(b'x'), kind);
+        assert_eq!(result.unwrap(), None);
+    }
+}
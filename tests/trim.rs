@@ -0,0 +1,79 @@
+use regex_automata::{Dfa, MaybeSymbol, Nfa, dfa_to_hir, equivalent};
+
+mod common;
+
+use common::dfa;
+
+/// DFA of `ab|ac` with an unreachable state 9 and a dead branch through 7
+fn with_garbage() -> Dfa<u8> {
+    let mut dfa = Dfa::new();
+    dfa.initial_states.insert(0);
+    dfa.accept_states.insert(3);
+    dfa.accept_states.insert(8);
+    dfa.link(0, 1, b'a');
+    dfa.link(1, 3, b'b');
+    dfa.link(1, 3, b'c');
+    dfa.link(0, 7, b'x');
+    dfa.link(7, 7, b'y');
+    dfa.link(9, 8, b'z');
+    dfa
+}
+
+#[test]
+fn reachable_and_coreachable() {
+    let dfa = with_garbage();
+
+    let mut reachable: Vec<_> = dfa.reachable_states().into_iter().collect();
+    reachable.sort();
+    assert_eq!(reachable, [0, 1, 3, 7]);
+
+    let mut coreachable: Vec<_> = dfa.coreachable_states().into_iter().collect();
+    coreachable.sort();
+    assert_eq!(coreachable, [0, 1, 3, 8, 9]);
+}
+
+#[test]
+fn trim() {
+    let mut dfa = with_garbage();
+    dfa.trim();
+
+    let mut states: Vec<_> = dfa.states_set().into_iter().collect();
+    states.sort();
+    assert_eq!(states, [0, 1, 3]);
+    assert_eq!(dfa.link_count(), 3);
+    assert_eq!(dfa.links_from(0).count(), 1);
+    assert_eq!(equivalent(&dfa, &self::dfa("ab|ac")), Ok(()));
+
+    assert_eq!(dfa_to_hir(with_garbage()).to_string(), "(?:a[bc])");
+}
+
+#[test]
+fn trim_empty_language() {
+    let mut dfa = self::dfa("[a&&b]");
+    dfa.link(0, 1, b'a');
+    dfa.trim();
+
+    assert_eq!(dfa.initial_state(), 0);
+    assert_eq!(dfa.link_count(), 0);
+    assert!(dfa.accept_states.is_empty());
+}
+
+#[test]
+fn renumber() {
+    let mut nfa = Nfa::new();
+    nfa.initial_states.insert(10);
+    nfa.accept_states.insert(30);
+    nfa.link(10, 20, MaybeSymbol::Symbol(b'a'));
+    nfa.link(20, 30, MaybeSymbol::Epsilon);
+
+    let renumbered = nfa.renumber();
+    assert_eq!(renumbered[&10], 0);
+    assert_eq!(renumbered[&20], 1);
+    assert_eq!(renumbered[&30], 2);
+
+    assert!(nfa.initial_states.contains(&0));
+    assert!(nfa.accept_states.contains(&2));
+    assert_eq!(nfa.links_from(1).next().unwrap().to, 2);
+    assert_eq!(nfa.links_to(1).next().unwrap().from, 0);
+    assert_eq!(nfa.next_counter().next(), 3);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{dfa, nfa};
+
+#[test]
+fn shortest_word() {
+    assert_eq!(dfa("[a-z]+@[a-z]+").shortest_word(), Some(b"a@a".to_vec()));
+    assert_eq!(dfa("cc|b+|ca").shortest_word(), Some(b"b".to_vec()));
+    assert_eq!(dfa("cc|ca").shortest_word(), Some(b"ca".to_vec()));
+    assert_eq!(dfa("a*").shortest_word(), Some(vec![]));
+    assert_eq!(dfa("[a&&b]").shortest_word(), None);
+
+    assert_eq!(nfa("cc|b+|ca").shortest_word_nfa(), Some(b"b".to_vec()));
+}
+
+#[test]
+fn smallest_word_of_len() {
+    let dfa = dfa("b[a-c]*|c{3}");
+    assert_eq!(dfa.smallest_word_of_len(3), Some(b"baa".to_vec()));
+    assert_eq!(dfa.smallest_word_of_len(1), Some(b"b".to_vec()));
+    assert_eq!(dfa.smallest_word_of_len(0), None);
+
+    let dfa = self::dfa("z|ab{2}c|b{3}");
+    assert_eq!(dfa.smallest_word_of_len(3), Some(b"bbb".to_vec()));
+    assert_eq!(dfa.smallest_word_of_len(4), Some(b"abbc".to_vec()));
+    assert_eq!(dfa.smallest_word_of_len(2), None);
+
+    assert_eq!(
+        nfa("x[0-9]{2}").smallest_word_of_len_nfa(3),
+        Some(b"x00".to_vec())
+    );
+}
+
+#[test]
+fn shortest_words() {
+    assert_eq!(
+        dfa("a*b").shortest_words(3),
+        vec![b"b".to_vec(), b"ab".to_vec(), b"aab".to_vec()]
+    );
+    assert_eq!(
+        dfa("[ba]{1,2}").shortest_words(4),
+        vec![b"a".to_vec(), b"b".to_vec(), b"aa".to_vec(), b"ab".to_vec()]
+    );
+    assert_eq!(
+        dfa("ab|c").shortest_words(5),
+        vec![b"c".to_vec(), b"ab".to_vec()]
+    );
+    assert_eq!(dfa("a+").shortest_words(0), Vec::<Vec<u8>>::new());
+    assert_eq!(
+        nfa("a|a|aa").shortest_words_nfa(3),
+        vec![b"a".to_vec(), b"aa".to_vec()]
+    );
+
+    // Long forced suffix, the prefixes that can't be completed are not explored
+    let mut zeros = vec![b'0'; 40];
+    assert_eq!(dfa("[a-z]*0{40}").shortest_words(1), vec![zeros.clone()]);
+    zeros.insert(0, b'a');
+    assert_eq!(dfa("[a-z]*0{40}").shortest_words(2)[1], zeros);
+}
+
+#[test]
+fn words_of_finite_language() {
+    let dfa = dfa("(GET|POST|PUT) /v[12]");
+    assert_eq!(
+        strings(dfa.words(usize::MAX)),
+        [
+            "GET /v1", "GET /v2", "PUT /v1", "PUT /v2", "POST /v1", "POST /v2"
+        ]
+    );
+    assert_eq!(
+        strings(dfa.words(7)),
+        ["GET /v1", "GET /v2", "PUT /v1", "PUT /v2"]
+    );
+    assert!(self::dfa("[a&&b]").words(10).next().is_none());
+}
+
+#[test]
+fn words_up_to_len() {
+    assert_eq!(strings(dfa("a*b").words(3)), ["b", "ab", "aab"]);
+    assert_eq!(strings(dfa("a*").words(0)), [""]);
+    assert_eq!(strings(dfa("[ab]*c").words(2)), ["c", "ac", "bc"]);
+    // Long dead ends are not explored
+    assert_eq!(strings(dfa("a|b{1000}").words(1000)).len(), 2);
+}
+
+#[test]
+fn count_words() {
+    let ids = dfa("[a-z]{2}[0-9]{6}");
+    assert_eq!(ids.count_words(8), 26 * 26 * 1_000_000);
+    assert_eq!(ids.count_words(7), 0);
+
+    assert_eq!(dfa("a*b").count_up_to(3), [0, 1, 1, 1]);
+    assert_eq!(dfa("[ab]*").count_up_to(4), [1, 2, 4, 8, 16]);
+    assert_eq!(
+        dfa("(GET|POST|PUT) /v[12]").count_up_to(8),
+        [0, 0, 0, 0, 0, 0, 0, 4, 2]
+    );
+    assert_eq!(dfa("[a&&b]").count_words(0), 0);
+
+    // Saturates instead of overflowing
+    assert_eq!(dfa("[0-9A-Za-z]*").count_words(22), u128::MAX);
+    assert!(dfa("[0-9A-Za-z]*").count_words(21) < u128::MAX);
+}
+
+fn strings(words: impl Iterator<Item = Vec<u8>>) -> Vec<String> {
+    words.map(|word| String::from_utf8(word).unwrap()).collect()
+}